@@ -1,5 +1,8 @@
 use actor12::prelude::*;
-use actor12::{Multi, MpscChannel, Call, spawn};
+use actor12::{Multi, SharedChannel, Call, spawn_pool};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::Duration;
 use std::future::Future;
 use tokio::time::sleep;
@@ -25,27 +28,19 @@ pub struct TaskResult {
     pub result: String,
 }
 
-#[derive(Debug)]
-pub struct GetWorkerStats;
-
-#[derive(Debug)]
-pub struct WorkerStats {
-    pub worker_id: u32,
-    pub tasks_processed: u32,
-}
-
 // Worker implementation
 impl Actor for Worker {
-    type Spec = u32; // worker_id
+    type Spec = Arc<AtomicU32>; // hands out worker ids
     type Message = Multi<Self>;
-    type Channel = MpscChannel<Self::Message>;
+    // One mailbox shared by every worker of the pool
+    type Channel = SharedChannel<Self::Message>;
     type Cancel = ();
     type State = ();
 
     fn state(_spec: &Self::Spec) -> Self::State {}
 
     fn init(ctx: Init<'_, Self>) -> impl Future<Output = Result<Self, Self::Cancel>> + Send + 'static {
-        let id = ctx.spec;
+        let id = ctx.spec.fetch_add(1, Ordering::SeqCst) + 1;
         async move {
             println!("Worker {} initialized", id);
             Ok(Worker {
@@ -78,27 +73,14 @@ impl Handler<Task> for Worker {
     }
 }
 
-impl Handler<GetWorkerStats> for Worker {
-    type Reply = Result<WorkerStats, anyhow::Error>;
-
-    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _msg: GetWorkerStats) -> Self::Reply {
-        Ok(WorkerStats {
-            worker_id: self.id,
-            tasks_processed: self.tasks_processed,
-        })
-    }
-}
-
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     println!("=== Worker Pool Example ===\n");
     
-    // Create workers
-    let worker1 = spawn::<Worker>(1);
-    let worker2 = spawn::<Worker>(2);
-    let worker3 = spawn::<Worker>(3);
+    // Create a pool of workers behind a single link
+    let pool = spawn_pool::<Worker>(3, Arc::new(AtomicU32::new(0)));
     
-    // Submit several tasks to different workers
+    // Submit several tasks; whichever worker is idle picks up the next one
     println!("Submitting tasks...\n");
     
     let tasks = vec![
@@ -114,11 +96,7 @@ async fn main() -> anyhow::Result<()> {
     let mut handles = vec![];
     
     for (i, (task_name, duration)) in tasks.into_iter().enumerate() {
-        let worker = match i % 3 {
-            0 => worker1.clone(),
-            1 => worker2.clone(),
-            _ => worker3.clone(),
-        };
+        let worker = pool.clone();
         
         let handle = tokio::spawn(async move {
             let task = Task {
@@ -134,20 +112,21 @@ async fn main() -> anyhow::Result<()> {
     
     // Wait for all tasks to complete
     println!("Waiting for all tasks to complete...\n");
+    let mut tasks_processed = BTreeMap::new();
     for handle in handles {
         match handle.await? {
-            Ok(result) => println!("✓ Task {} completed: {}", result.task_id, result.result),
+            Ok(result) => {
+                println!("✓ Task {} completed: {}", result.task_id, result.result);
+                *tasks_processed.entry(result.worker_id).or_insert(0) += 1;
+            }
             Err(e) => println!("✗ Task failed: {}", e),
         }
     }
     
-    // Get worker statistics
+    // Worker statistics
     println!("\n=== Worker Statistics ===");
-    for worker in [&worker1, &worker2, &worker3] {
-        match worker.ask_dyn(GetWorkerStats).await {
-            Ok(stats) => println!("Worker {}: {} tasks processed", stats.worker_id, stats.tasks_processed),
-            Err(e) => println!("Failed to get stats: {}", e),
-        }
+    for (worker_id, count) in tasks_processed {
+        println!("Worker {}: {} tasks processed", worker_id, count);
     }
     
     println!("\n=== All tasks completed ===");
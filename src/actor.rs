@@ -1,22 +1,31 @@
 use std::any::Any;
-use std::any::type_name;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::future::Future;
 use std::ops::ControlFlow;
-use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::cancel::CancelReason;
 use crate::cancel::CancelToken;
-use futures::FutureExt;
 use tokio::task::JoinSet;
-use tracing::Instrument;
 
 use crate::WeakLink;
+use crate::autoscale::QueueTime;
+use crate::behavior::Behavior;
+use crate::children::Children;
+use crate::crash::Crash;
+use crate::crash::HandlerPanic;
 use crate::channel::ActorChannel;
 use crate::channel::ActorReceiver as _;
+use crate::envelope::Envelope;
+use crate::error::StashFull;
 use crate::handler::Exec;
+use crate::handler::Handler;
 use crate::link::ActorLike;
 use crate::link::Link;
+use crate::multi::Multi;
+use crate::spawn::Spawner;
 
 /// Runtime context for an active actor instance.
 ///
@@ -33,6 +42,54 @@ pub struct ActorContext<A: ActorLike> {
     pub span: tracing::Span,
     /// Weak reference to the actor's link
     pub link: WeakLink<A>,
+    /// Child actors spawned with [`spawn_child`](Self::spawn_child)
+    pub children: Children,
+    /// Set when a handler panic asks for a restart, see [`HandlerPanic::Restart`]
+    pub(crate) restart: Option<Crash>,
+    /// [`Actor::on_handler_panic`], if handler panics are caught, see
+    /// [`Spawner::catch_handler_panics`]
+    pub(crate) isolate: Option<Isolate<A>>,
+    /// Where the time messages waited in the mailbox is reported, for an
+    /// [`AutoscalePool`](crate::AutoscalePool)
+    pub(crate) queue_time: Option<Arc<QueueTime>>,
+    /// Idle period after which the actor is passivated
+    pub(crate) passivate: Option<Duration>,
+    /// Set once the actor has been idle for `passivate`
+    pub(crate) passivating: bool,
+    /// See [`set_receive_timeout`](Self::set_receive_timeout)
+    pub(crate) receive_timeout: Option<ReceiveTimeoutTimer<A>>,
+    /// Messages put aside with [`stash`](Self::stash)
+    pub(crate) stash: VecDeque<A::Message>,
+    /// Messages given back by [`unstash_all`](Self::unstash_all), handled
+    /// before the mailbox
+    pub(crate) unstashed: VecDeque<A::Message>,
+    /// See [`set_behavior`](Self::set_behavior)
+    pub(crate) behavior: Option<Arc<Behavior<A>>>,
+}
+
+/// Delivered to an actor through its [`Handler`] when no message arrived
+/// within the period set with [`ActorContext::set_receive_timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceiveTimeout;
+
+/// Decides what a caught handler panic does to the actor.
+pub(crate) type Isolate<A> = fn(&mut A, &Crash) -> HandlerPanic;
+
+pub(crate) struct ReceiveTimeoutTimer<A: ActorLike> {
+    pub(crate) after: Duration,
+    pub(crate) message: fn() -> A::Message,
+}
+
+impl<A> ReceiveTimeoutTimer<A>
+where
+    A: Handler<ReceiveTimeout> + ActorLike<Message = Multi<A>>,
+{
+    fn new(after: Duration) -> Self {
+        Self {
+            after,
+            message: || Multi::new(Envelope::new(ReceiveTimeout).0),
+        }
+    }
 }
 
 impl<A: Actor> ActorContext<A> {
@@ -42,6 +99,106 @@ impl<A: Actor> ActorContext<A> {
     pub fn spawn(&mut self, future: impl Future<Output = ()> + Send + 'static) {
         self.futures.spawn(future);
     }
+
+    /// Spawn a child actor owned by this actor.
+    ///
+    /// The child's token is a child of this actor's token, so cancelling this
+    /// actor cancels the child (with the child's default reason). The child is
+    /// also stopped, and waited for, before this actor's
+    /// [`terminate`](Actor::terminate) runs.
+    pub fn spawn_child<C: Actor>(&mut self, spec: C::Spec) -> Link<C> {
+        self.spawn_child_with(Spawner::new(spec))
+    }
+
+    /// Like [`spawn_child`](Self::spawn_child), with the child's other
+    /// settings taken from `spawner`.
+    ///
+    /// A child with [`CrashPolicy::Escalate`](crate::CrashPolicy::Escalate)
+    /// escalates to this actor, which crashes in turn, unless the spawner
+    /// names its own [`on_escalate`](Spawner::on_escalate) handler.
+    pub fn spawn_child_with<C: Actor>(&mut self, spawner: Spawner<C>) -> Link<C> {
+        self.children.spawn(spawner, &self.token)
+    }
+
+    /// `true` while [`terminate`](Actor::terminate) runs because the actor is
+    /// being passivated rather than stopped, see
+    /// [`Spawner::passivate_after`](crate::Spawner::passivate_after).
+    pub fn is_passivating(&self) -> bool {
+        self.passivating
+    }
+
+    /// Deliver [`ReceiveTimeout`] whenever no message has arrived for `after`.
+    ///
+    /// The timer restarts with every message, including the
+    /// `ReceiveTimeout` itself, so an idle actor receives one every `after`.
+    /// Setting it again replaces the previous period. Only the default
+    /// [`cycle`](Actor::cycle) delivers it.
+    pub fn set_receive_timeout(&mut self, after: Duration)
+    where
+        A: Handler<ReceiveTimeout> + ActorLike<Message = Multi<A>>,
+    {
+        self.receive_timeout = Some(ReceiveTimeoutTimer::new(after));
+    }
+
+    /// Stop delivering [`ReceiveTimeout`].
+    pub fn cancel_receive_timeout(&mut self) {
+        self.receive_timeout = None;
+    }
+
+    /// Put a message aside until [`unstash_all`](Self::unstash_all).
+    ///
+    /// The stash holds at most [`Actor::stash_capacity`] messages; beyond
+    /// that the message is handed back in [`StashFull`]. Handlers can use
+    /// [`Call::stash`](crate::Call::stash) to stash the message they are
+    /// handling together with its reply.
+    pub fn stash(&mut self, msg: A::Message) -> Result<(), StashFull<A::Message>> {
+        if self.stash.len() >= A::stash_capacity() {
+            return Err(StashFull(msg));
+        }
+        self.stash.push_back(msg);
+        Ok(())
+    }
+
+    /// Hand every stashed message back, in the order it was stashed.
+    ///
+    /// They are handled before anything still waiting in the mailbox.
+    pub fn unstash_all(&mut self) {
+        while let Some(msg) = self.stash.pop_back() {
+            self.unstashed.push_front(msg);
+        }
+    }
+
+    /// Number of messages currently stashed.
+    pub fn stashed(&self) -> usize {
+        self.stash.len()
+    }
+
+    /// Handle the following messages according to `behavior`.
+    ///
+    /// Stashed messages are handed back (see [`unstash_all`](Self::unstash_all)),
+    /// so messages an earlier behavior put aside get another chance. A restart
+    /// goes back to the actor's plain [`Handler`] impls.
+    pub fn set_behavior(&mut self, behavior: Behavior<A>)
+    where
+        A: ActorLike<Message = Multi<A>>,
+    {
+        self.behavior = Some(Arc::new(behavior));
+        self.unstash_all();
+    }
+
+    /// Go back to handling every message with the actor's [`Handler`] impls.
+    ///
+    /// Stashed messages are handed back, as with
+    /// [`set_behavior`](Self::set_behavior).
+    pub fn reset_behavior(&mut self) {
+        self.behavior = None;
+        self.unstash_all();
+    }
+
+    /// Name of the active [`Behavior`], if any.
+    pub fn behavior(&self) -> Option<&'static str> {
+        self.behavior.as_ref().map(|behavior| behavior.name())
+    }
 }
 
 /// Initialization context provided to actors during startup.
@@ -57,6 +214,12 @@ pub struct Init<'a, A: Actor> {
     pub link: Link<A>,
     /// Cancellation token for the initialization process
     pub token: CancelToken<A::Cancel>,
+    /// Child actors spawned with [`spawn_child`](Self::spawn_child)
+    pub children: &'a mut Children,
+    /// See [`set_receive_timeout`](Self::set_receive_timeout)
+    pub(crate) receive_timeout: &'a mut Option<ReceiveTimeoutTimer<A>>,
+    /// See [`set_behavior`](Self::set_behavior)
+    pub(crate) behavior: &'a mut Option<Arc<Behavior<A>>>,
 }
 
 impl<A: Actor> Init<'_, A> {
@@ -70,11 +233,52 @@ impl<A: Actor> Init<'_, A> {
     {
         self.tasks.spawn(future);
     }
+
+    /// Spawn a child actor during initialization.
+    ///
+    /// See [`ActorContext::spawn_child`].
+    pub fn spawn_child<C: Actor>(&mut self, spec: C::Spec) -> Link<C> {
+        self.spawn_child_with(Spawner::new(spec))
+    }
+
+    /// Spawn a configured child actor during initialization.
+    ///
+    /// See [`ActorContext::spawn_child_with`].
+    pub fn spawn_child_with<C: Actor>(&mut self, spawner: Spawner<C>) -> Link<C> {
+        self.children.spawn(spawner, &self.token)
+    }
+
+    /// Start the receive timeout right away.
+    ///
+    /// See [`ActorContext::set_receive_timeout`].
+    pub fn set_receive_timeout(&mut self, after: Duration)
+    where
+        A: Handler<ReceiveTimeout> + ActorLike<Message = Multi<A>>,
+    {
+        *self.receive_timeout = Some(ReceiveTimeoutTimer::new(after));
+    }
+
+    /// Start in `behavior` rather than with the plain [`Handler`] impls.
+    ///
+    /// See [`ActorContext::set_behavior`].
+    pub fn set_behavior(&mut self, behavior: Behavior<A>)
+    where
+        A: ActorLike<Message = Multi<A>>,
+    {
+        *self.behavior = Some(Arc::new(behavior));
+    }
 }
 
+/// What an actor does with its mailbox once it has been cancelled.
 #[derive(Debug)]
 pub enum Terminate {
+    /// Close the mailbox to new sends, handle every message already queued,
+    /// then run [`Actor::terminate`].
+    ///
+    /// [`Link::wait`], [`Link::cancel_and_wait`] and [`Link::join`] all
+    /// resolve once the queue has been drained and `terminate` has run.
     ProcessAll,
+    /// Drop whatever is still queued and run [`Actor::terminate`] right away.
     Exit,
 }
 
@@ -106,8 +310,31 @@ pub trait Actor: Sized + Send + Sync + 'static {
         64
     }
 
+    /// How long requests to this actor wait for a reply by default, see
+    /// [`Ask`](crate::Ask). Defaults to no limit.
+    ///
+    /// It applies to the `ask*` methods of [`Link`](crate::Link),
+    /// [`WeakLink`](crate::WeakLink) and [`DynLink`](crate::DynLink), to
+    /// [`Link::request`](crate::Link::request), the non-blocking
+    /// [`Link::try_ask_dyn`](crate::Link::try_ask_dyn) and
+    /// [`Link::try_send`](crate::Link::try_send), and to
+    /// [`Permit::ask_dyn`](crate::Permit::ask_dyn).
+    /// [`Link::send`](crate::Link::send) waits however long it takes; use
+    /// `request` to send with a timeout.
+    fn ask_timeout() -> Option<Duration> {
+        None
+    }
+
+    /// Maximum number of messages [`ActorContext::stash`] holds. Defaults
+    /// to 64.
+    fn stash_capacity() -> usize {
+        64
+    }
+
     fn state(spec: &Self::Spec) -> Self::State;
 
+    /// Consulted once the actor's cycle has stopped, before
+    /// [`terminate`](Self::terminate). Defaults to [`Terminate::Exit`].
     fn termination_strategy(&mut self) -> Terminate {
         Terminate::Exit
     }
@@ -129,6 +356,13 @@ pub trait Actor: Sized + Send + Sync + 'static {
         ctx: &mut ActorContext<Self>,
     ) -> impl Future<Output = ControlFlow<CancelReason<Self::Cancel>, ()>> + Send {
         async {
+            if let Some(msg) = ctx.unstashed.pop_front() {
+                if let Some(msg) = msg.unless_expired() {
+                    Self::handle(self, Exec { ctx }, msg).await;
+                }
+                return ControlFlow::Continue(());
+            }
+
             tokio::select! {
                 reason = ctx.token.cancelled_or_dropped() => {
                     return ControlFlow::Break(reason.unwrap_or_default());
@@ -137,29 +371,51 @@ pub trait Actor: Sized + Send + Sync + 'static {
                     return cancel.map_break(CancelReason::new)
                 },
                 msg = ctx.rx.recv() => {
-                    match msg {
-                        Some(msg) => Self::handle(self, Exec { ctx }, msg).await,
+                    match msg.map(ActorMessage::unless_expired) {
+                        Some(Some(msg)) => Self::handle(self, Exec { ctx }, msg).await,
+                        Some(None) => {}
                         None => return ControlFlow::Break(Default::default()),
                     }
                 }
+                _ = idle(ctx.passivate), if ctx.passivate.is_some() => {
+                    ctx.passivating = true;
+                }
+                message = receive_timeout(&ctx.receive_timeout), if ctx.receive_timeout.is_some() => {
+                    Self::handle(self, Exec { ctx }, message).await
+                }
+                crash = ctx.children.escalated() => {
+                    // Crash in turn, so this actor's own crash policy applies.
+                    std::panic::resume_unwind(Box::new(crash));
+                }
             }
 
             ControlFlow::Continue(())
         }
     }
 
-    fn crash(payload: Box<dyn Any + Send>) -> impl Future<Output = ()> + Send {
-        async move {
-            let msg = match payload.downcast::<String>() {
-                Ok(s) => *s,
-                Err(payload) => match payload.downcast::<&'static str>() {
-                    Ok(s) => s.to_string(),
-                    Err(_) => "unknown panic".to_string(),
-                },
-            };
-            tracing::error!("ACTOR DIED: {msg}");
-            std::process::exit(-1);
-        }
+    /// Called with the raw panic payload when the actor's task panics.
+    ///
+    /// Exiting the process is now the job of
+    /// [`CrashPolicy::Abort`](crate::CrashPolicy::Abort); this hook only
+    /// observes the crash.
+    #[deprecated(note = "implement `on_crash`, which receives the decoded `Crash`")]
+    fn crash(_payload: Box<dyn Any + Send>) -> impl Future<Output = ()> + Send {
+        futures::future::ready(())
+    }
+
+    /// Called when the actor's task panics, before its
+    /// [`CrashPolicy`](crate::CrashPolicy) is applied.
+    fn on_crash(_crash: &Crash) -> impl Future<Output = ()> + Send {
+        futures::future::ready(())
+    }
+
+    /// Consulted when handling a single message panics. Defaults to
+    /// [`HandlerPanic::Propagate`], which crashes the actor as before.
+    ///
+    /// Only consulted for actors spawned with
+    /// [`Spawner::catch_handler_panics`], and only for [`Multi`] messages.
+    fn on_handler_panic(&mut self, _crash: &Crash) -> HandlerPanic {
+        HandlerPanic::Propagate
     }
 
     fn handle<'a>(
@@ -173,87 +429,40 @@ pub trait Actor: Sized + Send + Sync + 'static {
     fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self>;
 
     fn spawn(spec: Self::Spec) -> Link<Self> {
-        let count = crate::count::Count::<Self>::new();
-
-        let (tx, rx) = Self::Channel::create(Self::mailbox_capacity());
-        let token = CancelToken::<Self::Cancel>::new();
-
-        let mut link: Link<Self> = Link::new(tx, token.clone(), Self::state(&spec));
-        let mut join_set = JoinSet::default();
-
-        let weak = link.downgrade();
-        let span = Self::span(&spec);
-
-        let state = Self::init(Init {
-            spec: spec,
-            token: token.clone(),
-            tasks: &mut join_set,
-            link: link.clone(),
-        });
-
-        let handle = tokio::spawn(
-            {
-                let span = span.clone();
-                async move {
-                    // Keep the live-instance counter alive for the actor's whole lifetime.
-                    let _count_guard = count;
-
-                    let lifecycle = async move {
-                        let state = state.in_current_span().await;
-
-                        let mut state = match state {
-                            Ok(state) => state,
-                            Err(cancel) => {
-                                tracing::error!(
-                                    reason = ?cancel,
-                                    "Actor terminated before initialization"
-                                );
-                                token.cancel(cancel);
-                                return;
-                            }
-                        };
-
-                        let mut ctx = ActorContext {
-                            rx,
-                            token,
-                            futures: join_set,
-                            span: span.clone(),
-                            link: weak,
-                        };
-
-                        let reason = loop {
-                            match Self::cycle(&mut state, &mut ctx).in_current_span().await {
-                                ControlFlow::Continue(_) => {}
-                                ControlFlow::Break(reason) => break reason,
-                            }
-                        };
-
-                        Actor::terminate(state, ctx, reason).in_current_span().await;
-                    };
-
-                    // Catch panics inline so we don't need a separate monitor task.
-                    match AssertUnwindSafe(lifecycle).catch_unwind().await {
-                        Ok(()) => {
-                            tracing::info!("Actor {} completed gracefully", type_name::<Self>());
-                        }
-                        Err(payload) => {
-                            tracing::error!("Actor {} crashed", type_name::<Self>());
-                            Self::crash(payload).await;
-                        }
-                    }
-                }
-            }
-            .instrument(span.clone()),
-        );
+        Spawner::new(spec).spawn()
+    }
+}
 
-        link.set_monitor(handle);
-        link
+/// Resolves once the actor has been idle for `period`. Lazy, so that actors
+/// that never passivate do not start a timer for every message.
+async fn idle(period: Option<Duration>) {
+    match period {
+        Some(period) => tokio::time::sleep(period).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Resolves with the [`ReceiveTimeout`] message once `timer` expires.
+async fn receive_timeout<A: ActorLike>(timer: &Option<ReceiveTimeoutTimer<A>>) -> A::Message {
+    match timer {
+        Some(timer) => {
+            tokio::time::sleep(timer.after).await;
+            (timer.message)()
+        }
+        None => std::future::pending().await,
     }
 }
 
 pub trait ActorMessage<A: ActorLike>: SyncTrait {
     fn handle<'a>(self, state: &'a mut A, ctx: Exec<'a, A>)
     -> impl Future<Output = ()> + Send + 'a;
+
+    /// The message, unless it has outlived its deadline. An expired message
+    /// is answered with [`ActorError::Expired`](crate::ActorError::Expired)
+    /// and dropped.
+    fn unless_expired(self) -> Option<Self> {
+        Some(self)
+    }
 }
 
 pub trait SyncTrait: Sized + Send + Sync + 'static {}
@@ -0,0 +1,125 @@
+use std::future::Future;
+use std::future::IntoFuture;
+use std::time::Duration;
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use futures::future::Either;
+use tokio::time::Instant;
+
+use crate::error::ActorError;
+use crate::error::FromError;
+
+/// Sends a message. Resolves to the future of the reply, or straight to the
+/// reply if sending failed.
+pub(crate) type Request<'a, R> = BoxFuture<'a, Result<BoxFuture<'static, R>, R>>;
+
+/// A request to an actor, created by [`Link::ask`](crate::Link::ask) and
+/// friends.
+///
+/// Awaiting it sends the message and waits for the reply. Unless changed
+/// with [`timeout`](Self::timeout) or [`no_timeout`](Self::no_timeout), it
+/// waits for at most [`Actor::ask_timeout`](crate::Actor::ask_timeout), then
+/// resolves to [`ActorError::Timeout`] converted into the reply type.
+///
+/// ```ignore
+/// let count = link.ask(Increment).timeout(Duration::from_secs(1)).await?;
+/// ```
+#[must_use = "an ask does nothing unless awaited"]
+pub struct Ask<'a, R> {
+    request: Request<'a, R>,
+    timeout: Option<Duration>,
+}
+
+impl<'a, R> Ask<'a, R>
+where
+    R: FromError<ActorError> + Send + 'static,
+{
+    pub(crate) fn new(request: Request<'a, R>, timeout: Option<Duration>) -> Self {
+        Self { request, timeout }
+    }
+
+    /// Give up after `timeout`, counted from the first poll and covering the
+    /// send as well as the reply.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Wait for the reply however long it takes.
+    pub fn no_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+
+    /// Send the message now and return the future of its reply, like
+    /// [`Link::ask_dyn_async`](crate::Link::ask_dyn_async).
+    ///
+    /// The timeout covers the send and the reply together.
+    pub async fn deferred(self) -> BoxFuture<'static, R> {
+        let deadline = Deadline::after(self.timeout);
+        match deadline.run(self.request).await {
+            Ok(Ok(reply)) => async move { deadline.run(reply).await.unwrap_or_else(R::from_err) }.boxed(),
+            Ok(Err(reply)) => std::future::ready(reply).boxed(),
+            Err(err) => std::future::ready(R::from_err(err)).boxed(),
+        }
+    }
+}
+
+impl<'a, R> IntoFuture for Ask<'a, R>
+where
+    R: FromError<ActorError> + Send + 'static,
+{
+    type Output = R;
+    type IntoFuture = BoxFuture<'a, R>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let timeout = self.timeout;
+        let reply = async move {
+            match self.request.await {
+                Ok(reply) => reply.await,
+                Err(reply) => reply,
+            }
+        };
+        within(timeout, reply).boxed()
+    }
+}
+
+/// Runs `future` for at most `timeout`, then resolves to
+/// [`ActorError::Timeout`] converted into the reply. Without a timeout the
+/// future is polled as is, so untimed requests don't pay for the deadline.
+pub(crate) fn within<R, F>(timeout: Option<Duration>, future: F) -> Either<F, impl Future<Output = R>>
+where
+    F: Future<Output = R>,
+    R: FromError<ActorError>,
+{
+    match timeout {
+        None => Either::Left(future),
+        Some(_) => {
+            let deadline = Deadline::after(timeout);
+            Either::Right(async move { deadline.run(future).await.unwrap_or_else(R::from_err) })
+        }
+    }
+}
+
+/// The point in time a request gives up at, if any.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Deadline(Option<(Instant, Duration)>);
+
+impl Deadline {
+    /// A timeout too long to represent as an instant means no deadline.
+    pub(crate) fn after(timeout: Option<Duration>) -> Self {
+        Self(timeout.and_then(|timeout| Some((Instant::now().checked_add(timeout)?, timeout))))
+    }
+
+    /// Runs `future` to completion, or fails with [`ActorError::Timeout`]
+    /// once the deadline has passed.
+    pub(crate) async fn run<T>(self, future: impl Future<Output = T>) -> Result<T, ActorError> {
+        match self.0 {
+            None => Ok(future.await),
+            Some((at, timeout)) => tokio::time::timeout_at(at, future)
+                .await
+                .map_err(|_| ActorError::Timeout(timeout)),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use tokio::time::Instant;
+use tokio::time::MissedTickBehavior;
+
+use crate::actor::Actor;
+use crate::actor::SyncTrait;
+use crate::drop::DropHandle;
+use crate::handler::Handler;
+use crate::link::ActorLike;
+use crate::link::Link;
+use crate::multi::Multi;
+use crate::router::Router;
+use crate::router::Routing;
+use crate::spawn::Spawner;
+
+/// When an [`AutoscalePool`] adds and retires instances.
+#[derive(Debug, Clone)]
+pub struct AutoscalePolicy {
+    /// Fewest instances; the pool starts with this many and replaces
+    /// instances that stop below it.
+    pub min: usize,
+    /// Most instances.
+    pub max: usize,
+    /// Add an instance when the average number of queued messages per
+    /// instance reaches this.
+    pub scale_up_depth: f64,
+    /// Add an instance when messages waited this long on average in the
+    /// instances' mailboxes over the last interval, from being sent to being
+    /// handled. Only [`Multi`] messages are timed.
+    pub scale_up_latency: Option<Duration>,
+    /// Retire an idle instance when the average number of queued messages
+    /// per instance is at most this.
+    pub scale_down_depth: f64,
+    /// Least time between two changes of the pool size, so that one burst
+    /// does not make the pool swing back and forth.
+    pub cooldown: Duration,
+    /// How often the load is sampled.
+    pub interval: Duration,
+}
+
+impl Default for AutoscalePolicy {
+    fn default() -> Self {
+        Self {
+            min: 1,
+            max: 8,
+            scale_up_depth: 8.0,
+            scale_up_latency: None,
+            scale_down_depth: 0.0,
+            cooldown: Duration::from_secs(10),
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// A pool of actors that grows and shrinks with its load.
+///
+/// The pool keeps between [`min`](AutoscalePolicy::min) and
+/// [`max`](AutoscalePolicy::max) instances of an actor behind a
+/// [`Router`] with [`Routing::LeastLoaded`]. Every
+/// [`interval`](AutoscalePolicy::interval) it samples the members' mailboxes
+/// and how long their messages waited in them, however they were sent, then
+/// adds or retires at most one instance, and none within a
+/// [`cooldown`](AutoscalePolicy::cooldown) of the last change. Sampling
+/// follows [`tokio::time`], so it follows tokio's paused clock in tests.
+///
+/// Messages go to the instance with the shortest mailbox. The instance with
+/// the fewest queued messages is retired: it is taken out of the router
+/// first, and on a later sample, once it has handled what was already queued
+/// for it, it is cancelled and the scaler waits for it to exit.
+/// Dropping the pool stops every instance.
+///
+/// [`Spawner::autoscale`] spawns the instances with the builder's settings,
+/// e.g. in an [`ActorSystem`](crate::ActorSystem) or with a
+/// [`CrashPolicy`](crate::CrashPolicy).
+pub struct AutoscalePool<A: Actor> {
+    shared: Arc<Shared<A>>,
+    _scaler: DropHandle<()>,
+}
+
+struct Shared<A: Actor> {
+    router: Router<A>,
+}
+
+/// Collects how long messages waited in the mailboxes of a pool's instances.
+#[derive(Default)]
+pub(crate) struct QueueTime {
+    /// Total wait and count of the messages since the last sample.
+    waited: Mutex<(Duration, u32)>,
+}
+
+impl QueueTime {
+    /// A message sent at `sent` is being handled.
+    pub(crate) fn record(&self, sent: Instant) {
+        let mut waited = self.waited.lock();
+        waited.0 += sent.elapsed();
+        waited.1 += 1;
+    }
+
+    /// Average wait since the last call, if any messages were handled.
+    fn take(&self) -> Option<Duration> {
+        let (total, count) = std::mem::take(&mut *self.waited.lock());
+        (count > 0).then(|| total / count)
+    }
+}
+
+impl<A: Actor> AutoscalePool<A>
+where
+    A::Spec: Clone,
+{
+    /// Spawn [`min`](AutoscalePolicy::min) instances from copies of `spec`
+    /// and start scaling them by `policy`.
+    ///
+    /// Shorthand for `Spawner::new(spec).autoscale(policy)`.
+    pub fn spawn(spec: A::Spec, policy: AutoscalePolicy) -> Self {
+        Spawner::new(spec).autoscale(policy)
+    }
+
+    /// See [`Spawner::autoscale`].
+    pub(crate) fn start(spawner: Spawner<A>, policy: AutoscalePolicy) -> Self {
+        assert!(policy.min > 0, "an autoscaling pool needs at least one instance");
+        assert!(policy.min <= policy.max, "an autoscaling pool needs min <= max");
+
+        let shared = Arc::new(Shared {
+            router: Router::new(Routing::LeastLoaded),
+        });
+        let queue_time = Arc::new(QueueTime::default());
+        let scaler = Scaler {
+            shared: shared.clone(),
+            spawner: spawner.report_queue_time(queue_time.clone()),
+            queue_time,
+            policy,
+            changed: None,
+            retiring: Vec::new(),
+        };
+        scaler.fill();
+
+        Self {
+            shared,
+            _scaler: DropHandle(tokio::spawn(scaler.run())),
+        }
+    }
+}
+
+impl<A: Actor> AutoscalePool<A> {
+    /// The router over the current instances.
+    pub fn router(&self) -> &Router<A> {
+        &self.shared.router
+    }
+
+    /// Number of instances.
+    pub fn len(&self) -> usize {
+        self.shared.router.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Like [`Link::ask_dyn`], to the least loaded instance.
+    pub async fn ask_dyn<T>(&self, message: T) -> <A as Handler<T>>::Reply
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        self.shared.router.ask_dyn(message).await
+    }
+
+    /// Like [`Link::tell_dyn`], to the least loaded instance.
+    pub async fn tell_dyn<T>(&self, message: T)
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        self.shared.router.tell_dyn(message).await
+    }
+}
+
+/// The task sampling the load and resizing the pool.
+struct Scaler<A: Actor> {
+    shared: Arc<Shared<A>>,
+    spawner: Spawner<A>,
+    queue_time: Arc<QueueTime>,
+    policy: AutoscalePolicy,
+    /// When the pool size last changed.
+    changed: Option<Instant>,
+    /// Instances taken out of the router on an earlier sample that may
+    /// still have queued messages.
+    retiring: Vec<Link<A>>,
+}
+
+impl<A: Actor> Scaler<A>
+where
+    A::Spec: Clone,
+{
+    async fn run(mut self) {
+        let mut interval = tokio::time::interval(self.policy.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The first tick is immediate.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            self.sample().await;
+        }
+    }
+
+    /// Spawn instances until there are `min`; returns the members.
+    fn fill(&self) -> Vec<Link<A>> {
+        let mut members = self.shared.router.prune();
+        while members.len() < self.policy.min {
+            let link = self.spawner.member().spawn();
+            self.shared.router.add(link.clone());
+            members.push(link);
+        }
+        members
+    }
+
+    /// Stop the retiring instances that have handled their queued messages,
+    /// and wait for them to exit.
+    async fn retire(&mut self) {
+        let (busy, idle) = std::mem::take(&mut self.retiring)
+            .into_iter()
+            .partition(|link| link.alive() && !link.is_empty());
+        self.retiring = busy;
+        for link in idle {
+            link.cancel_and_wait(A::Cancel::default()).await;
+        }
+    }
+
+    async fn sample(&mut self) {
+        self.retire().await;
+        let members = self.fill();
+        let latency = self.queue_time.take();
+
+        let queued: usize = members.iter().map(Link::len).sum();
+        let depth = queued as f64 / members.len() as f64;
+        let busy = depth >= self.policy.scale_up_depth
+            || latency.is_some_and(|latency| self.policy.scale_up_latency.is_some_and(|max| latency >= max));
+
+        if self.changed.is_some_and(|changed| changed.elapsed() < self.policy.cooldown) {
+            return;
+        }
+
+        if busy && members.len() < self.policy.max {
+            tracing::debug!(depth, ?latency, "Adding an instance of {}", std::any::type_name::<A>());
+            self.shared.router.add(self.spawner.member().spawn());
+            self.changed = Some(Instant::now());
+        } else if !busy && depth <= self.policy.scale_down_depth && members.len() > self.policy.min {
+            // The least loaded instance goes first, the newest of them on a tie.
+            let Some(idle) = members.iter().rev().min_by_key(|link| link.len()) else {
+                return;
+            };
+            tracing::debug!(depth, ?latency, "Retiring an instance of {}", std::any::type_name::<A>());
+            // A sender may have picked it just before it left the router, so it
+            // is stopped on a later sample, once such a message has arrived.
+            self.shared.router.remove(idle);
+            self.retiring.push(idle.clone());
+            self.changed = Some(Instant::now());
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+
+use crate::actor::Actor;
+use crate::actor::ActorContext;
+use crate::actor::SyncTrait;
+use crate::error::ActorError;
+use crate::handler::Call;
+use crate::handler::Exec;
+use crate::handler::Handler;
+use crate::link::ActorLike;
+use crate::multi::Multi;
+
+/// A handler for `M` that [`Behavior::on`] uses in place of `A`'s own
+/// [`Handler<M>`] impl.
+pub type HandlerFn<A, M> = for<'a> fn(
+    &'a mut A,
+    Call<'a, A, <A as Handler<M>>::Reply>,
+    M,
+) -> BoxFuture<'a, <A as Handler<M>>::Reply>;
+
+/// The set of messages a [`Multi`] actor handles in one phase of its
+/// protocol, installed with [`ActorContext::set_behavior`].
+///
+/// While a behavior is active, only the message types it lists are handled:
+/// either by the actor's own [`Handler`] impl ([`handle`](Self::handle)) or by
+/// a phase-specific function ([`on`](Self::on)). Anything else is rejected
+/// with [`ActorError::Unhandled`] or stashed until the next switch, see
+/// [`Unhandled`].
+///
+/// ```ignore
+/// let connecting = Behavior::new("connecting")
+///     .handle::<Connected>()
+///     .unhandled(Unhandled::Stash);
+///
+/// let connected = Behavior::new("connected")
+///     .handle::<Query>()
+///     .on::<Connected>(|_, _, _| async { Err(anyhow!("already connected")) }.boxed());
+/// ```
+pub struct Behavior<A: ActorLike> {
+    name: &'static str,
+    unhandled: Unhandled,
+    routes: HashMap<TypeId, Arc<dyn Route<A>>>,
+    stash: StashFn<A>,
+}
+
+/// Stashes a message, or hands it back if the stash is full.
+type StashFn<A> = fn(&mut ActorContext<A>, Multi<A>) -> Result<(), Multi<A>>;
+
+/// What a [`Behavior`] does with a message it does not list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Unhandled {
+    /// Answer the sender with [`ActorError::Unhandled`].
+    #[default]
+    Reject,
+    /// Put the message aside with [`ActorContext::stash`], to be handled
+    /// after the next behavior switch. If the stash is full the sender gets
+    /// [`ActorError::StashFull`].
+    Stash,
+}
+
+impl<A> Behavior<A>
+where
+    A: Actor<Message = Multi<A>>,
+{
+    /// A behavior that handles nothing yet.
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            unhandled: Unhandled::default(),
+            routes: HashMap::new(),
+            stash: |ctx, msg| ctx.stash(msg).map_err(|full| full.0),
+        }
+    }
+
+    /// Handle `M` with the actor's [`Handler<M>`] impl.
+    pub fn handle<M: SyncTrait>(mut self) -> Self
+    where
+        A: Handler<M>,
+    {
+        self.routes.insert(Multi::<A>::key_of::<M>(), Arc::new(Delegate));
+        self
+    }
+
+    /// Handle `M` with `handler` while this behavior is active.
+    pub fn on<M: SyncTrait>(mut self, handler: HandlerFn<A, M>) -> Self
+    where
+        A: Handler<M>,
+    {
+        self.routes
+            .insert(Multi::<A>::key_of::<M>(), Arc::new(Override(handler)));
+        self
+    }
+
+    /// Set what happens to messages this behavior does not list.
+    pub fn unhandled(mut self, unhandled: Unhandled) -> Self {
+        self.unhandled = unhandled;
+        self
+    }
+
+    /// `true` if `M` is handled while this behavior is active.
+    pub fn handles<M: SyncTrait>(&self) -> bool
+    where
+        A: Handler<M>,
+    {
+        self.routes.contains_key(&Multi::<A>::key_of::<M>())
+    }
+}
+
+impl<A: ActorLike> Behavior<A> {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub(crate) fn dispatch<'a>(
+        &self,
+        msg: Multi<A>,
+        state: &'a mut A,
+        ctx: Exec<'a, A>,
+    ) -> BoxFuture<'a, ()> {
+        if let Some(route) = self.routes.get(&msg.key()) {
+            return route.handle(msg, state, ctx);
+        }
+
+        match self.unhandled {
+            Unhandled::Stash => {
+                if let Err(msg) = (self.stash)(ctx.ctx, msg) {
+                    msg.handler.reject(ActorError::StashFull);
+                }
+            }
+            Unhandled::Reject => {
+                let err = ActorError::Unhandled {
+                    behavior: self.name,
+                    message: msg.handler.message_name(),
+                };
+                msg.handler.reject(err);
+            }
+        }
+        std::future::ready(()).boxed()
+    }
+}
+
+impl<A: ActorLike> Clone for Behavior<A> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name,
+            unhandled: self.unhandled,
+            routes: self.routes.clone(),
+            stash: self.stash,
+        }
+    }
+}
+
+trait Route<A: ActorLike>: Send + Sync {
+    fn handle<'a>(&self, msg: Multi<A>, state: &'a mut A, ctx: Exec<'a, A>) -> BoxFuture<'a, ()>;
+}
+
+/// Routes to the actor's own [`Handler`] impl.
+struct Delegate;
+
+impl<A: ActorLike> Route<A> for Delegate {
+    fn handle<'a>(&self, msg: Multi<A>, state: &'a mut A, ctx: Exec<'a, A>) -> BoxFuture<'a, ()> {
+        msg.handler.handle(state, ctx)
+    }
+}
+
+/// Routes to a [`HandlerFn`].
+struct Override<A: Handler<M>, M: SyncTrait>(HandlerFn<A, M>);
+
+impl<A, M> Route<A> for Override<A, M>
+where
+    A: Handler<M>,
+    M: SyncTrait,
+{
+    fn handle<'a>(&self, msg: Multi<A>, state: &'a mut A, ctx: Exec<'a, A>) -> BoxFuture<'a, ()> {
+        let deadline = msg.handler.deadline();
+        match msg.downcast::<M>() {
+            Ok(envelope) => Multi::handle_with(envelope, deadline, state, ctx, self.0),
+            Err(_) => unreachable!("routed by message type"),
+        }
+    }
+}
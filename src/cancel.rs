@@ -4,6 +4,8 @@ use std::ops::Deref;
 use std::panic::Location;
 use std::sync::Arc;
 use std::sync::OnceLock;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
 use parking_lot::Mutex;
 use tokio::sync::watch::Receiver;
@@ -86,6 +88,32 @@ impl<T: Clone> CancelToken<T> {
 			inner: self.inner.child(),
 		}
 	}
+
+	/// Calls `f` once when this token is cancelled, or right away if it
+	/// already is. `f` runs on the thread that cancels the token, so it
+	/// must not block.
+	pub(crate) fn on_cancel<F>(&self, f: F)
+	where
+		T: Send + Sync + 'static,
+		F: FnOnce(&CancelReason<T>) + Send + 'static,
+	{
+		self.inner.on_cancel(f)
+	}
+
+	/// Like [`child`](Self::child), for a token with a different reason type.
+	///
+	/// When this token is cancelled, the child is cancelled with the reason
+	/// produced by `map`, keeping the original cancel location.
+	pub fn child_map<U, F>(&self, map: F) -> CancelToken<U>
+	where
+		T: Send + Sync + 'static,
+		U: Clone + Send + Sync + 'static,
+		F: Fn(&T) -> U + Send + Sync + 'static,
+	{
+		CancelToken {
+			inner: self.inner.child_map(map),
+		}
+	}
 }
 
 #[derive(Debug)]
@@ -121,6 +149,14 @@ impl<T> CancelReason<T> {
 	pub fn new_with_loc(value: T, location: &'static Location<'static>) -> Self {
 		Self { value, location }
 	}
+
+	pub fn value(&self) -> &T {
+		&self.value
+	}
+
+	pub fn location(&self) -> &'static Location<'static> {
+		self.location
+	}
 }
 
 impl<T> Clone for CancelReason<T>
@@ -139,6 +175,65 @@ pub struct TreeNode<T: Clone> {
 	pub state: Sender<State<T>>,
 	pub children: parking_lot::Mutex<Vec<Arc<TreeNode<T>>>>,
 	pub drop: OnceLock<T>,
+	mapped: parking_lot::Mutex<Vec<Box<dyn MappedChild<T>>>>,
+	/// Lengths of `children` and `mapped` after they were last pruned.
+	children_pruned: AtomicUsize,
+	mapped_pruned: AtomicUsize,
+}
+
+/// Forgets the entries of `list` that are no longer in use, once it has
+/// doubled since it was last pruned to `pruned` entries. Pruning on every
+/// insertion would make adding `n` entries O(n²).
+fn prune<E>(list: &mut Vec<E>, pruned: &AtomicUsize, in_use: impl FnMut(&E) -> bool) {
+	if list.len() >= 2 * pruned.load(Ordering::Relaxed).max(8) {
+		list.retain(in_use);
+		pruned.store(list.len(), Ordering::Relaxed);
+	}
+}
+
+/// A child node with a different reason type, see [`CancelToken::child_map`].
+trait MappedChild<T>: Send + Sync {
+	fn cancel_with_reason(&self, reason: &CancelReason<T>);
+	fn in_use(&self) -> bool;
+}
+
+struct Mapped<U: Clone, F> {
+	node: Arc<TreeNode<U>>,
+	map: F,
+}
+
+/// A callback run on cancellation, see [`CancelToken::on_cancel`].
+struct OnCancel<F>(Mutex<Option<F>>);
+
+impl<T, F> MappedChild<T> for OnCancel<F>
+where
+	F: FnOnce(&CancelReason<T>) + Send,
+{
+	fn cancel_with_reason(&self, reason: &CancelReason<T>) {
+		if let Some(f) = self.0.lock().take() {
+			f(reason)
+		}
+	}
+
+	fn in_use(&self) -> bool {
+		self.0.lock().is_some()
+	}
+}
+
+impl<T, U, F> MappedChild<T> for Mapped<U, F>
+where
+	U: Clone + Send + Sync,
+	F: Fn(&T) -> U + Send + Sync,
+{
+	fn cancel_with_reason(&self, reason: &CancelReason<T>) {
+		let value = (self.map)(reason.value());
+		self.node
+			.cancel_with_reason(CancelReason::new_with_loc(value, reason.location()))
+	}
+
+	fn in_use(&self) -> bool {
+		self.node.in_use()
+	}
 }
 
 impl<T: Clone> Drop for TreeNode<T> {
@@ -156,11 +251,17 @@ impl<T: Clone> TreeNode<T> {
 			state: Sender::new(State::Running),
 			children: Mutex::new(Vec::new()),
 			drop: OnceLock::new(),
+			mapped: Mutex::new(Vec::new()),
+			children_pruned: AtomicUsize::new(0),
+			mapped_pruned: AtomicUsize::new(0),
 		})
 	}
 
 	pub fn reset(&self) {
 		self.children.lock().clear();
+		self.mapped.lock().clear();
+		self.children_pruned.store(0, Ordering::Relaxed);
+		self.mapped_pruned.store(0, Ordering::Relaxed);
 		self.state.send_replace(State::Running);
 	}
 
@@ -173,6 +274,9 @@ impl<T: Clone> TreeNode<T> {
 		let mut children = self.children.lock();
 		match *self.state.borrow() {
 			State::Running => {
+				// Forget children whose tokens are all gone, so that long-lived
+				// parents (e.g. supervisors) don't accumulate them.
+				prune(&mut children, &self.children_pruned, |child| child.in_use());
 				let node = TreeNode::new();
 				children.push(node.clone());
 				node
@@ -183,6 +287,60 @@ impl<T: Clone> TreeNode<T> {
 		}
 	}
 
+	/// Returns `false` once no token holds this node and it has no child
+	/// left to pass a cancellation on to.
+	fn in_use(self: &Arc<Self>) -> bool {
+		Arc::strong_count(self) > 1
+			|| !self.children.lock().is_empty()
+			|| !self.mapped.lock().is_empty()
+	}
+
+	fn child_map<U, F>(&self, map: F) -> Arc<TreeNode<U>>
+	where
+		T: Send + Sync + 'static,
+		U: Clone + Send + Sync + 'static,
+		F: Fn(&T) -> U + Send + Sync + 'static,
+	{
+		let mut mapped = self.mapped.lock();
+		let node = TreeNode::new();
+		match &*self.state.borrow() {
+			State::Running => {
+				prune(&mut mapped, &self.mapped_pruned, |child| child.in_use());
+				mapped.push(Box::new(Mapped {
+					node: node.clone(),
+					map,
+				}));
+			}
+			State::Cancelled(reason) => {
+				let value = map(reason.value());
+				node.cancel_with_reason(CancelReason::new_with_loc(value, reason.location()));
+			}
+		}
+		node
+	}
+
+	fn on_cancel<F>(&self, f: F)
+	where
+		T: Send + Sync + 'static,
+		F: FnOnce(&CancelReason<T>) + Send + 'static,
+	{
+		let mut mapped = self.mapped.lock();
+		let reason = match &*self.state.borrow() {
+			State::Running => None,
+			State::Cancelled(reason) => Some(reason.clone()),
+		};
+		match reason {
+			None => {
+				prune(&mut mapped, &self.mapped_pruned, |child| child.in_use());
+				mapped.push(Box::new(OnCancel(Mutex::new(Some(f)))));
+			}
+			Some(reason) => {
+				drop(mapped);
+				f(&reason)
+			}
+		}
+	}
+
 	pub async fn cancelled(mut recv: Receiver<State<T>>) -> CancelReason<T>
 	where
 		T: Clone,
@@ -244,6 +402,9 @@ impl<T: Clone> TreeNode<T> {
 			for child in children.deref() {
 				child.cancel_with_reason(reason.clone())
 			}
+			for child in self.mapped.lock().iter() {
+				child.cancel_with_reason(&reason)
+			}
 		}
 	}
 }
\ No newline at end of file
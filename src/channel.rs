@@ -1,5 +1,17 @@
+use std::any::type_name;
 use std::future::Future;
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::task::Poll;
+
+use tokio::sync::Mutex;
+use tokio::sync::Notify;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
+use tokio::sync::TryAcquireError;
 
 use tokio::sync::mpsc;
 
@@ -34,6 +46,11 @@ where
 	fn recv(&mut self) -> impl Future<Output = Option<T>> + Send;
 	#[allow(unused)]
 	fn is_closed(&mut self) -> bool;
+	/// `true` if no message is waiting to be received.
+	fn is_empty(&mut self) -> bool;
+	/// Stops accepting new messages. Messages already queued can still be
+	/// received, after which `recv` returns `None`.
+	fn close(&mut self);
 }
 
 impl<T> ActorReceiver<T> for mpsc::Receiver<T>
@@ -47,6 +64,14 @@ where
 	fn is_closed(&mut self) -> bool {
 		mpsc::Receiver::is_closed(self)
 	}
+
+	fn is_empty(&mut self) -> bool {
+		mpsc::Receiver::is_empty(self)
+	}
+
+	fn close(&mut self) {
+		mpsc::Receiver::close(self)
+	}
 }
 
 pub trait ActorSender<T>: Send
@@ -54,9 +79,46 @@ where
 	T: Send,
 {
 	type Error: std::error::Error + std::marker::Send + std::marker::Sync;
+	type Permit: ActorPermit<T>;
 	fn send(&self, value: T) -> impl Future<Output = Result<(), Self::Error>> + Send;
+	/// Like [`send`](Self::send), into the lane for `priority`. Channels
+	/// without lanes ignore the priority.
+	fn send_with_priority(
+		&self,
+		value: T,
+		_priority: Priority,
+	) -> impl Future<Output = Result<(), Self::Error>> + Send {
+		self.send(value)
+	}
+	/// Queues `value` only if that can be done without waiting.
+	fn try_send(&self, value: T) -> Result<(), TrySendError<T, Self::Error>>;
+	/// Waits for a free slot and holds it until the permit is used or
+	/// dropped. `None` once the channel is closed.
+	fn reserve(&self) -> impl Future<Output = Option<Self::Permit>> + Send;
 	fn closed(&self) -> impl Future<Output = ()> + Send;
 	fn is_closed(&self) -> bool;
+	/// Number of free slots; reserved slots are not free.
+	fn capacity(&self) -> usize;
+	/// Number of slots of the empty channel.
+	fn max_capacity(&self) -> usize;
+	/// Number of messages dropped on overflow, see
+	/// [`OverflowChannel`](crate::OverflowChannel).
+	fn dropped(&self) -> u64 {
+		0
+	}
+}
+
+/// A slot reserved with [`ActorSender::reserve`].
+pub trait ActorPermit<T>: Send + Sync {
+	fn send(self, value: T);
+}
+
+/// Why [`ActorSender::try_send`] failed.
+pub enum TrySendError<T, E> {
+	/// The mailbox is full; the message is handed back.
+	Full(T),
+	/// The mailbox is closed, reported as [`ActorSender::send`] would.
+	Closed(E),
 }
 
 impl<T> ActorSender<T> for mpsc::Sender<T>
@@ -64,11 +126,23 @@ where
 	T: Send + Sync + 'static,
 {
 	type Error = mpsc::error::SendError<T>;
+	type Permit = mpsc::OwnedPermit<T>;
 
 	async fn send(&self, value: T) -> Result<(), Self::Error> {
 		mpsc::Sender::send(self, value).await
 	}
 
+	fn try_send(&self, value: T) -> Result<(), TrySendError<T, Self::Error>> {
+		mpsc::Sender::try_send(self, value).map_err(|err| match err {
+			mpsc::error::TrySendError::Full(value) => TrySendError::Full(value),
+			mpsc::error::TrySendError::Closed(value) => TrySendError::Closed(mpsc::error::SendError(value)),
+		})
+	}
+
+	async fn reserve(&self) -> Option<Self::Permit> {
+		self.clone().reserve_owned().await.ok()
+	}
+
 	async fn closed(&self) -> () {
 		mpsc::Sender::closed(self).await
 	}
@@ -76,4 +150,503 @@ where
 	fn is_closed(&self) -> bool {
 		mpsc::Sender::is_closed(self)
 	}
+
+	fn capacity(&self) -> usize {
+		mpsc::Sender::capacity(self)
+	}
+
+	fn max_capacity(&self) -> usize {
+		mpsc::Sender::max_capacity(self)
+	}
+}
+
+impl<T> ActorPermit<T> for mpsc::OwnedPermit<T>
+where
+	T: Send + Sync + 'static,
+{
+	fn send(self, value: T) {
+		mpsc::OwnedPermit::send(self, value);
+	}
+}
+
+/// A bounded mailbox whose receiver can be cloned, so several actor
+/// instances can take turns on one mailbox.
+///
+/// Used by [`Spawner::pool`](crate::Spawner::pool): every idle worker waits
+/// on the mailbox and the first in line gets the next message, so a slow
+/// message only holds up the worker handling it. Closing any of the
+/// receivers closes the mailbox for all of them.
+pub struct SharedChannel<T> {
+	_t: PhantomData<T>,
+}
+
+impl<T> ActorChannel for SharedChannel<T>
+where
+	T: Send + Sync + 'static,
+{
+	type Message = T;
+	type Receiver = SharedReceiver<T>;
+	type Sender = mpsc::Sender<T>;
+	fn create(buffer: usize) -> (Self::Sender, Self::Receiver) {
+		let (tx, rx) = mpsc::channel(buffer);
+		let rx = SharedReceiver {
+			shared: Arc::new(Shared {
+				rx: Mutex::new(rx),
+				closing: AtomicBool::new(false),
+				close: Notify::new(),
+			}),
+		};
+		(tx, rx)
+	}
+}
+
+struct Shared<T> {
+	rx: Mutex<mpsc::Receiver<T>>,
+	closing: AtomicBool,
+	/// Wakes the receiver currently holding `rx`, so it can close it.
+	close: Notify,
+}
+
+pub struct SharedReceiver<T> {
+	shared: Arc<Shared<T>>,
+}
+
+impl<T> Clone for SharedReceiver<T> {
+	fn clone(&self) -> Self {
+		Self {
+			shared: self.shared.clone(),
+		}
+	}
+}
+
+impl<T> ActorReceiver<T> for SharedReceiver<T>
+where
+	T: Send + Sync,
+{
+	async fn recv(&mut self) -> Option<T> {
+		// Waiting receivers queue up on the lock in order, and only the one
+		// holding it waits for a message.
+		let mut rx = self.shared.rx.lock().await;
+		loop {
+			let close = self.shared.close.notified();
+			if self.shared.closing.load(Ordering::Acquire) {
+				rx.close();
+				return rx.recv().await;
+			}
+			tokio::select! {
+				value = rx.recv() => return value,
+				_ = close => {}
+			}
+		}
+	}
+
+	fn is_closed(&mut self) -> bool {
+		match self.shared.rx.try_lock() {
+			Ok(rx) => rx.is_closed(),
+			Err(_) => self.shared.closing.load(Ordering::Acquire),
+		}
+	}
+
+	fn is_empty(&mut self) -> bool {
+		match self.shared.rx.try_lock() {
+			Ok(rx) => rx.is_empty(),
+			// Another receiver is taking a message.
+			Err(_) => false,
+		}
+	}
+
+	fn close(&mut self) {
+		self.shared.closing.store(true, Ordering::Release);
+		match self.shared.rx.try_lock() {
+			Ok(mut rx) => rx.close(),
+			// Whoever holds the lock closes it on our behalf.
+			Err(_) => self.shared.close.notify_waiters(),
+		}
+	}
+}
+
+/// A mailbox that never makes senders wait.
+///
+/// Meant for control-plane actors, where a blocked sender is worse than a
+/// growing mailbox (e.g. two actors that ask each other). The buffer size
+/// the actor asks for ([`Actor::mailbox_capacity`](crate::Actor::mailbox_capacity))
+/// is used as a high-water mark instead: a warning is logged each time the
+/// queue grows to it, then to twice as much, four times as much, and so on.
+pub struct UnboundedChannel<T> {
+	_t: PhantomData<T>,
+}
+
+impl<T> ActorChannel for UnboundedChannel<T>
+where
+	T: Send + Sync + 'static,
+{
+	type Message = T;
+	type Receiver = UnboundedReceiver<T>;
+	type Sender = UnboundedSender<T>;
+	fn create(buffer: usize) -> (Self::Sender, Self::Receiver) {
+		let (tx, rx) = mpsc::unbounded_channel();
+		let queue = Arc::new(Queue {
+			len: AtomicUsize::new(0),
+			high_water: buffer.max(1),
+		});
+		let tx = UnboundedSender {
+			tx,
+			queue: queue.clone(),
+		};
+		(tx, UnboundedReceiver { rx, queue })
+	}
+}
+
+/// Length of an unbounded queue, shared by its two ends.
+struct Queue {
+	len: AtomicUsize,
+	high_water: usize,
+}
+
+impl Queue {
+	fn push<T>(&self) {
+		let len = self.len.fetch_add(1, Ordering::Relaxed) + 1;
+		if len.is_multiple_of(self.high_water) && (len / self.high_water).is_power_of_two() {
+			tracing::warn!("Unbounded mailbox for {} holds {} messages", type_name::<T>(), len);
+		}
+	}
+
+	fn pop(&self) {
+		self.len.fetch_sub(1, Ordering::Relaxed);
+	}
+}
+
+pub struct UnboundedSender<T> {
+	tx: mpsc::UnboundedSender<T>,
+	queue: Arc<Queue>,
+}
+
+impl<T> Clone for UnboundedSender<T> {
+	fn clone(&self) -> Self {
+		Self {
+			tx: self.tx.clone(),
+			queue: self.queue.clone(),
+		}
+	}
+}
+
+impl<T> UnboundedSender<T> {
+	fn push(&self, value: T) -> Result<(), mpsc::error::SendError<T>> {
+		// Count first, so the receiver never sees more messages than counted.
+		self.queue.push::<T>();
+		self.tx.send(value).inspect_err(|_| self.queue.pop())
+	}
+}
+
+impl<T> ActorSender<T> for UnboundedSender<T>
+where
+	T: Send + Sync + 'static,
+{
+	type Error = mpsc::error::SendError<T>;
+	type Permit = UnboundedPermit<T>;
+
+	async fn send(&self, value: T) -> Result<(), Self::Error> {
+		self.push(value)
+	}
+
+	fn try_send(&self, value: T) -> Result<(), TrySendError<T, Self::Error>> {
+		self.push(value).map_err(TrySendError::Closed)
+	}
+
+	async fn reserve(&self) -> Option<Self::Permit> {
+		if self.tx.is_closed() {
+			return None;
+		}
+		Some(UnboundedPermit { tx: self.clone() })
+	}
+
+	async fn closed(&self) -> () {
+		self.tx.closed().await
+	}
+
+	fn is_closed(&self) -> bool {
+		self.tx.is_closed()
+	}
+
+	fn capacity(&self) -> usize {
+		usize::MAX - self.queue.len.load(Ordering::Relaxed)
+	}
+
+	fn max_capacity(&self) -> usize {
+		usize::MAX
+	}
+}
+
+/// There is always room in an unbounded mailbox, so the permit only holds
+/// the sender.
+pub struct UnboundedPermit<T> {
+	tx: UnboundedSender<T>,
+}
+
+impl<T> ActorPermit<T> for UnboundedPermit<T>
+where
+	T: Send + Sync + 'static,
+{
+	fn send(self, value: T) {
+		let _ = self.tx.push(value);
+	}
+}
+
+pub struct UnboundedReceiver<T> {
+	rx: mpsc::UnboundedReceiver<T>,
+	queue: Arc<Queue>,
+}
+
+impl<T> ActorReceiver<T> for UnboundedReceiver<T>
+where
+	T: Send + Sync,
+{
+	async fn recv(&mut self) -> Option<T> {
+		let value = self.rx.recv().await;
+		if value.is_some() {
+			self.queue.pop();
+		}
+		value
+	}
+
+	fn is_closed(&mut self) -> bool {
+		self.rx.is_closed()
+	}
+
+	fn is_empty(&mut self) -> bool {
+		self.rx.is_empty()
+	}
+
+	fn close(&mut self) {
+		self.rx.close()
+	}
+}
+
+/// Which lane of a [`PriorityChannel`] a message goes to.
+///
+/// Higher priorities are received first. Above every user priority is a
+/// system lane the crate keeps for its control messages, such as
+/// [`Down`](crate::Down); it is not limited by the mailbox capacity, so they
+/// get through even when the mailbox is full of data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Priority(Lane);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Lane {
+	User(u8),
+	System,
+}
+
+impl Default for Lane {
+	fn default() -> Self {
+		Lane::User(0)
+	}
+}
+
+impl Priority {
+	/// The lowest user priority, used when none is given.
+	pub const NORMAL: Priority = Priority(Lane::User(0));
+	/// The lane for control messages sent by the crate itself.
+	pub(crate) const SYSTEM: Priority = Priority(Lane::System);
+
+	/// User priority `level`. Levels beyond the channel's lanes go to its
+	/// highest user lane.
+	pub const fn new(level: u8) -> Self {
+		Priority(Lane::User(level))
+	}
+}
+
+/// A mailbox with `LANES` user priority lanes plus a system lane for
+/// control messages.
+///
+/// The receiver always takes the oldest message of the highest non-empty
+/// lane, so messages stay FIFO within a lane. User lanes share the mailbox
+/// capacity; the system lane is unbounded. Plain sends use
+/// [`Priority::NORMAL`].
+pub struct PriorityChannel<T, const LANES: usize = 3> {
+	_t: PhantomData<T>,
+}
+
+impl<T, const LANES: usize> ActorChannel for PriorityChannel<T, LANES>
+where
+	T: Send + Sync + 'static,
+{
+	type Message = T;
+	type Receiver = PriorityReceiver<T>;
+	type Sender = PrioritySender<T>;
+	fn create(buffer: usize) -> (Self::Sender, Self::Receiver) {
+		assert!(LANES > 0, "a priority channel needs at least one user lane");
+		// The system lane comes last.
+		let (senders, receivers) = (0..=LANES).map(|_| mpsc::unbounded_channel()).unzip();
+		let slots = Arc::new(Semaphore::new(buffer));
+		let tx = PrioritySender {
+			lanes: Arc::new(senders),
+			slots: slots.clone(),
+			buffer,
+		};
+		let rx = PriorityReceiver {
+			lanes: receivers,
+			slots,
+		};
+		(tx, rx)
+	}
+}
+
+pub struct PrioritySender<T> {
+	lanes: Arc<Vec<mpsc::UnboundedSender<T>>>,
+	slots: Arc<Semaphore>,
+	buffer: usize,
+}
+
+impl<T> Clone for PrioritySender<T> {
+	fn clone(&self) -> Self {
+		Self {
+			lanes: self.lanes.clone(),
+			slots: self.slots.clone(),
+			buffer: self.buffer,
+		}
+	}
+}
+
+impl<T> PrioritySender<T> {
+	fn lane(&self, priority: Priority) -> &mpsc::UnboundedSender<T> {
+		let system = self.lanes.len() - 1;
+		match priority.0 {
+			Lane::System => &self.lanes[system],
+			Lane::User(level) => &self.lanes[(level as usize).min(system - 1)],
+		}
+	}
+
+	/// Queues `value` into a user lane, using a slot already taken.
+	fn push(
+		&self,
+		value: T,
+		priority: Priority,
+		slot: OwnedSemaphorePermit,
+	) -> Result<(), mpsc::error::SendError<T>> {
+		self.lane(priority).send(value)?;
+		// The receiver gives the slot back once it takes the message.
+		slot.forget();
+		Ok(())
+	}
+}
+
+impl<T> ActorSender<T> for PrioritySender<T>
+where
+	T: Send + Sync + 'static,
+{
+	type Error = mpsc::error::SendError<T>;
+	type Permit = PriorityPermit<T>;
+
+	async fn send(&self, value: T) -> Result<(), Self::Error> {
+		self.send_with_priority(value, Priority::NORMAL).await
+	}
+
+	async fn send_with_priority(&self, value: T, priority: Priority) -> Result<(), Self::Error> {
+		if priority == Priority::SYSTEM {
+			return self.lane(priority).send(value);
+		}
+		match self.slots.clone().acquire_owned().await {
+			Ok(slot) => self.push(value, priority, slot),
+			Err(_) => Err(mpsc::error::SendError(value)),
+		}
+	}
+
+	fn try_send(&self, value: T) -> Result<(), TrySendError<T, Self::Error>> {
+		match self.slots.clone().try_acquire_owned() {
+			Ok(slot) => self.push(value, Priority::NORMAL, slot).map_err(TrySendError::Closed),
+			Err(TryAcquireError::NoPermits) => Err(TrySendError::Full(value)),
+			Err(TryAcquireError::Closed) => Err(TrySendError::Closed(mpsc::error::SendError(value))),
+		}
+	}
+
+	async fn reserve(&self) -> Option<Self::Permit> {
+		let slot = self.slots.clone().acquire_owned().await.ok()?;
+		Some(PriorityPermit {
+			tx: self.clone(),
+			slot,
+		})
+	}
+
+	async fn closed(&self) -> () {
+		self.lanes[0].closed().await
+	}
+
+	fn is_closed(&self) -> bool {
+		self.lanes[0].is_closed()
+	}
+
+	fn capacity(&self) -> usize {
+		self.slots.available_permits()
+	}
+
+	fn max_capacity(&self) -> usize {
+		self.buffer
+	}
+}
+
+/// A slot reserved in a [`PriorityChannel`]; sends at [`Priority::NORMAL`].
+pub struct PriorityPermit<T> {
+	tx: PrioritySender<T>,
+	slot: OwnedSemaphorePermit,
+}
+
+impl<T> ActorPermit<T> for PriorityPermit<T>
+where
+	T: Send + Sync + 'static,
+{
+	fn send(self, value: T) {
+		let _ = self.tx.push(value, Priority::NORMAL, self.slot);
+	}
+}
+
+pub struct PriorityReceiver<T> {
+	lanes: Vec<mpsc::UnboundedReceiver<T>>,
+	slots: Arc<Semaphore>,
+}
+
+impl<T> ActorReceiver<T> for PriorityReceiver<T>
+where
+	T: Send + Sync,
+{
+	async fn recv(&mut self) -> Option<T> {
+		let system = self.lanes.len() - 1;
+		std::future::poll_fn(|cx| {
+			let mut closed = 0;
+			// Highest lane first.
+			for (lane, rx) in self.lanes.iter_mut().enumerate().rev() {
+				match rx.poll_recv(cx) {
+					Poll::Ready(Some(value)) => {
+						if lane != system {
+							self.slots.add_permits(1);
+						}
+						return Poll::Ready(Some(value));
+					}
+					Poll::Ready(None) => closed += 1,
+					Poll::Pending => {}
+				}
+			}
+			if closed == self.lanes.len() {
+				Poll::Ready(None)
+			} else {
+				Poll::Pending
+			}
+		})
+		.await
+	}
+
+	fn is_closed(&mut self) -> bool {
+		self.lanes[0].is_closed()
+	}
+
+	fn is_empty(&mut self) -> bool {
+		self.lanes.iter().all(mpsc::UnboundedReceiver::is_empty)
+	}
+
+	fn close(&mut self) {
+		self.slots.close();
+		for rx in &mut self.lanes {
+			rx.close();
+		}
+	}
 }
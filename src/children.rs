@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use tokio::sync::mpsc;
+
+use crate::actor::Actor;
+use crate::cancel::CancelToken;
+use crate::crash::Crash;
+use crate::link::Link;
+use crate::monitor::ExitReceiver;
+use crate::monitor::exited;
+use crate::spawn::Spawner;
+use crate::system::WeakSystem;
+
+/// Actors spawned by an actor with
+/// [`ActorContext::spawn_child`](crate::ActorContext::spawn_child) or
+/// [`Init::spawn_child`](crate::Init::spawn_child).
+///
+/// Children are tracked without keeping them alive: a child still stops when
+/// its last link is dropped. Whatever is still running when the parent stops
+/// is stopped, newest first, before the parent's
+/// [`terminate`](crate::Actor::terminate) runs.
+pub struct Children {
+    entries: Vec<Box<dyn Child>>,
+    /// Number of entries after finished children were last dropped.
+    pruned: usize,
+    /// The system the parent runs in, which its children join as well.
+    system: Option<WeakSystem>,
+    /// Crashes escalated by children, see
+    /// [`CrashPolicy::Escalate`](crate::CrashPolicy::Escalate).
+    escalations: mpsc::UnboundedSender<Crash>,
+    escalated: mpsc::UnboundedReceiver<Crash>,
+}
+
+impl Default for Children {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl Children {
+    pub(crate) fn new(system: Option<WeakSystem>) -> Self {
+        let (escalations, escalated) = mpsc::unbounded_channel();
+        Self {
+            entries: Vec::new(),
+            pruned: 0,
+            system,
+            escalations,
+            escalated,
+        }
+    }
+
+    /// Number of children that have not finished yet.
+    pub fn len(&self) -> usize {
+        self.entries.iter().filter(|child| child.running()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Spawns a child under `parent`, in the parent's system, escalating its
+    /// crashes to this set.
+    pub(crate) fn spawn<A, P>(&mut self, spawner: Spawner<A>, parent: &CancelToken<P>) -> Link<A>
+    where
+        A: Actor,
+        P: Clone + Send + Sync + 'static,
+    {
+        let escalations = self.escalations.clone();
+        let escalate = Arc::new(move |crash| {
+            let _ = escalations.send(crash);
+        });
+        let system = self.system.as_ref().and_then(WeakSystem::upgrade);
+        let link = spawner.child_of(parent, system, escalate).spawn();
+        self.push(&link);
+        link
+    }
+
+    /// Resolves with the next crash a child escalated.
+    pub(crate) async fn escalated(&mut self) -> Crash {
+        match self.escalated.recv().await {
+            Some(crash) => crash,
+            // `escalations` keeps the channel open.
+            None => std::future::pending().await,
+        }
+    }
+
+    pub(crate) fn push<A: Actor>(&mut self, link: &Link<A>) {
+        // Drop finished children once the list has doubled, so that pushing
+        // stays amortised O(1) however many children are live.
+        if self.entries.len() >= 2 * self.pruned.max(8) {
+            self.entries.retain(|child| child.running());
+            self.pruned = self.entries.len();
+        }
+        self.entries.push(Box::new(Entry::<A> {
+            token: link.state.token.clone(),
+            exit: link.state.exit.subscribe(),
+        }));
+    }
+
+    /// Cancels every child still running and waits for each to finish, in
+    /// reverse spawn order.
+    pub(crate) async fn stop(&mut self) {
+        while let Some(child) = self.entries.pop() {
+            child.stop().await;
+        }
+        self.pruned = 0;
+    }
+}
+
+trait Child: Send + Sync {
+    fn running(&self) -> bool;
+    fn stop(&self) -> BoxFuture<'static, ()>;
+}
+
+struct Entry<A: Actor> {
+    token: CancelToken<A::Cancel>,
+    exit: ExitReceiver<A::Cancel>,
+}
+
+impl<A: Actor> Child for Entry<A> {
+    fn running(&self) -> bool {
+        self.exit.borrow().is_none()
+    }
+
+    fn stop(&self) -> BoxFuture<'static, ()> {
+        self.token.cancel(A::Cancel::default());
+        exited(self.exit.clone()).map(drop).boxed()
+    }
+}
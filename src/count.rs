@@ -3,6 +3,7 @@ use std::any::type_name;
 use std::collections::BTreeMap;
 use std::fmt;
 use std::marker::PhantomData;
+use std::sync::Arc;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 
@@ -38,22 +39,86 @@ pub fn counts() -> &'static DashMap<TypeId, Stats> {
     COUNTS.get_or_init(DashMap::new)
 }
 
+/// Statistics kept apart from the global map, e.g. per
+/// [`ActorSystem`](crate::ActorSystem).
+///
+/// Cloning a registry yields another handle to the same statistics.
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    counts: Arc<DashMap<TypeId, Stats>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of live instances of `T` counted in this registry.
+    pub fn live<T: 'static>(&self) -> usize {
+        self.load::<T>(|stats| &stats.live)
+    }
+
+    /// Number of instances of `T` ever counted in this registry.
+    pub fn total<T: 'static>(&self) -> usize {
+        self.load::<T>(|stats| &stats.total)
+    }
+
+    /// A snapshot of this registry's statistics.
+    pub fn report(&self) -> Report {
+        Report::from_counts(&self.counts)
+    }
+
+    fn load<T: 'static>(&self, field: impl Fn(&Stats) -> &AtomicUsize) -> usize {
+        self.counts
+            .get(&TypeId::of::<T>())
+            .map_or(0, |stats| field(&stats).load(Ordering::Relaxed))
+    }
+}
+
+/// Where a [`Count`] is recorded.
+#[derive(Debug)]
+enum Counts {
+    Global,
+    Registry(Arc<DashMap<TypeId, Stats>>),
+}
+
+impl Counts {
+    fn map(&self) -> &DashMap<TypeId, Stats> {
+        match self {
+            Counts::Global => counts(),
+            Counts::Registry(counts) => counts,
+        }
+    }
+}
+
 /// A guard that increments a counter when created and decrements it when dropped.
 ///
 /// This helps track the number of live instances of a specific type `T`.
 #[derive(Debug)]
 pub struct Count<T: 'static> {
+    counts: Counts,
     _phantom: PhantomData<T>,
 }
 
 impl<T: 'static> Count<T> {
     /// Creates a new `Count` instance, incrementing the counters for type `T`.
     pub fn new() -> Self {
+        Self::record(Counts::Global)
+    }
+
+    /// Like [`new`](Self::new), but counts the instance in `registry` instead
+    /// of the global map.
+    pub fn new_in(registry: &Registry) -> Self {
+        Self::record(Counts::Registry(registry.counts.clone()))
+    }
+
+    fn record(counts: Counts) -> Self {
         let type_id = TypeId::of::<T>();
         let type_name = type_name::<T>();
 
         // Get or insert the stats for this type.
-        let entry = counts()
+        let entry = counts
+            .map()
             .entry(type_id)
             .or_insert_with(|| Stats::new(type_name));
 
@@ -63,8 +128,10 @@ impl<T: 'static> Count<T> {
         // Increment live instances and update max_live if necessary.
         let live = entry.live.fetch_add(1, Ordering::Relaxed) + 1;
         entry.max_live.fetch_max(live, Ordering::Relaxed);
+        drop(entry);
 
         Self {
+            counts,
             _phantom: PhantomData,
         }
     }
@@ -73,7 +140,7 @@ impl<T: 'static> Count<T> {
 impl<T: 'static> Drop for Count<T> {
     fn drop(&mut self) {
         let type_id = TypeId::of::<T>();
-        if let Some(entry) = counts().get(&type_id) {
+        if let Some(entry) = self.counts.map().get(&type_id) {
             // Decrement the live count.
             entry.live.fetch_sub(1, Ordering::Relaxed);
         }
@@ -96,8 +163,12 @@ pub struct Report {
 impl Report {
     #[allow(dead_code)]
     fn new() -> Self {
+        Self::from_counts(counts())
+    }
+
+    fn from_counts(counts: &DashMap<TypeId, Stats>) -> Self {
         let mut by_type = BTreeMap::new();
-        for entry in counts().iter() {
+        for entry in counts.iter() {
             let stats = entry.value();
             by_type.insert(
                 stats.type_name,
@@ -0,0 +1,119 @@
+use std::any::Any;
+use std::any::type_name;
+use std::sync::Arc;
+
+use crate::actor::ActorContext;
+use crate::actor::Isolate;
+use crate::link::ActorLike;
+
+/// A panic caught in an actor's task.
+#[derive(Debug, Clone)]
+pub struct Crash {
+    /// Type name of the actor that panicked.
+    pub actor: &'static str,
+    /// The decoded panic message.
+    pub message: String,
+}
+
+impl Crash {
+    pub(crate) fn new<A>(payload: &(dyn Any + Send)) -> Self {
+        Self {
+            actor: type_name::<A>(),
+            message: panic_message(payload),
+        }
+    }
+}
+
+impl std::fmt::Display for Crash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} panicked: {}", self.actor, self.message)
+    }
+}
+
+/// Decodes the message of a caught panic.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else if let Some(s) = payload.downcast_ref::<&'static str>() {
+        s.to_string()
+    } else if let Some(crash) = payload.downcast_ref::<Crash>() {
+        crash.to_string()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Receives crashes escalated by child actors, see [`CrashPolicy::Escalate`].
+pub(crate) type Escalation = Arc<dyn Fn(Crash) + Send + Sync>;
+
+/// What happens to an actor after its task panics.
+///
+/// Set per actor with [`Spawner::crash_policy`](crate::Spawner::crash_policy).
+/// Whatever the policy, the crash is logged and passed to
+/// [`Actor::on_crash`](crate::Actor::on_crash) first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CrashPolicy {
+    /// Stop only the crashed actor. Its token is cancelled with the default
+    /// reason, so anything spawned under it stops as well, and it exits with
+    /// [`ActorExit::Crashed`](crate::ActorExit::Crashed).
+    #[default]
+    Stop,
+    /// Stop the actor and hand the [`Crash`] to its parent, registered with
+    /// [`Spawner::on_escalate`](crate::Spawner::on_escalate). Without a
+    /// parent this behaves like [`Stop`](Self::Stop).
+    ///
+    /// A child spawned with
+    /// [`ActorContext::spawn_child_with`](crate::ActorContext::spawn_child_with)
+    /// escalates to the actor that spawned it, which then crashes in turn
+    /// and applies its own policy.
+    Escalate,
+    /// Re-initialise the actor from its spec behind the same link, keeping
+    /// the messages still queued in its mailbox. Requires
+    /// [`Spawner::restartable`](crate::Spawner::restartable); restarts are paced
+    /// by the spawner's [`RestartPolicy`](crate::RestartPolicy) and escalate
+    /// once its limit is exceeded.
+    Restart,
+    /// Exit the whole process, as earlier releases always did.
+    Abort,
+}
+
+/// What an actor does when one of its handlers panics, see
+/// [`Actor::on_handler_panic`](crate::Actor::on_handler_panic) and
+/// [`Spawner::catch_handler_panics`](crate::Spawner::catch_handler_panics).
+///
+/// Whatever the choice, a caller waiting on the panicked message's reply
+/// receives [`ActorError::HandlerPanicked`](crate::ActorError::HandlerPanicked).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HandlerPanic {
+    /// Keep the actor's current state and go on with the next message.
+    Resume,
+    /// Re-initialise the actor from its spec behind the same link, as
+    /// [`CrashPolicy::Restart`] does, whatever its crash policy. Requires
+    /// [`Spawner::restartable`](crate::Spawner::restartable).
+    Restart,
+    /// Let the panic take down the actor's task, which then goes through its
+    /// [`CrashPolicy`].
+    #[default]
+    Propagate,
+}
+
+/// Applies `isolate`, the actor's
+/// [`on_handler_panic`](crate::Actor::on_handler_panic), to a panic caught
+/// while handling a message.
+pub(crate) fn isolated<A: ActorLike>(
+    state: &mut A,
+    ctx: &mut ActorContext<A>,
+    isolate: Isolate<A>,
+    payload: Box<dyn Any + Send>,
+) {
+    let crash = Crash::new::<A>(&*payload);
+    match isolate(state, &crash) {
+        HandlerPanic::Resume => {
+            tracing::warn!("Actor {} resumed after a handler panic: {}", crash.actor, crash.message);
+        }
+        HandlerPanic::Restart => {
+            ctx.restart = Some(crash);
+        }
+        HandlerPanic::Propagate => std::panic::resume_unwind(payload),
+    }
+}
@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::time::Duration;
 
 use crate::channel::{ActorChannel, ActorSender};
 use crate::link::ActorLike;
@@ -20,6 +21,61 @@ pub enum ActorError {
 
 	#[error("Async reply")]
 	AsyncReply,
+
+	#[error("Restart limit exceeded")]
+	RestartLimitExceeded,
+
+	#[error("Handler panicked: {0}")]
+	HandlerPanicked(String),
+
+	#[error("Stash is full")]
+	StashFull,
+
+	#[error("Message stashed")]
+	Stashed,
+
+	#[error("Dropped by mailbox overflow")]
+	Overflow,
+
+	#[error("Router has no members")]
+	NoMembers,
+
+	#[error("Message expired before it was handled")]
+	Expired,
+
+	#[error("No reply within {0:?}")]
+	Timeout(Duration),
+
+	#[error("{message} is not handled in behavior {behavior}")]
+	Unhandled {
+		behavior: &'static str,
+		message: &'static str,
+	},
+}
+
+/// Returned by [`ActorContext::stash`](crate::ActorContext::stash) when the
+/// stash is at capacity. Carries the message back.
+#[derive(thiserror::Error)]
+#[error("Stash is full")]
+pub struct StashFull<M>(pub M);
+
+impl<M> std::fmt::Debug for StashFull<M> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("StashFull(..)")
+	}
+}
+
+/// Returned by the `try_` sends of [`Link`](crate::Link), such as
+/// [`try_tell_dyn`](crate::Link::try_tell_dyn), when the mailbox is full.
+/// Carries the message back.
+#[derive(thiserror::Error)]
+#[error("Mailbox is full")]
+pub struct Full<M>(pub M);
+
+impl<M> std::fmt::Debug for Full<M> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("Full(..)")
+	}
 }
 
 pub trait FromError<E> {
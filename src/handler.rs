@@ -4,14 +4,17 @@ use std::ops::DerefMut;
 
 use take_once::TakeOnce;
 use tokio::sync::oneshot::error::RecvError;
+use tokio::time::Instant;
 
 use crate::actor::Actor;
 use crate::actor::ActorContext;
 use crate::actor::SyncTrait;
+use crate::envelope::Envelope;
 use crate::error::ActorError;
 use crate::error::ActorSendError;
 use crate::error::FromError;
 use crate::link::ActorLike;
+use crate::multi::Multi;
 
 pub trait Handler<M>: ActorLike
 where
@@ -38,6 +41,8 @@ pub struct Exec<'a, A: ActorLike> {
 pub struct Call<'a, A: ActorLike, R> {
     pub(crate) reply: &'a TakeOnce<tokio::sync::oneshot::Sender<R>>,
     pub ctx: Exec<'a, A>,
+    /// When the message being handled expires, see [`Multi::expires_at`].
+    pub(crate) deadline: Option<Instant>,
 }
 
 impl<'a, A, R> Deref for Call<'a, A, R>
@@ -86,6 +91,34 @@ where
 
         return R::from_err(ActorError::AsyncReply);
     }
+
+    /// Stash the message being handled, together with its reply, see
+    /// [`ActorContext::stash`].
+    ///
+    /// The caller gets its reply once the message is unstashed and handled
+    /// again. Returns the value the handler should return: a placeholder
+    /// that is never delivered, or [`ActorError::StashFull`] for the caller
+    /// if the stash is at capacity.
+    pub fn stash<M>(&mut self, msg: M) -> R
+    where
+        M: SyncTrait,
+        A: Handler<M, Reply = R> + ActorLike<Message = Multi<A>>,
+    {
+        if self.ctx.stashed() >= A::stash_capacity() {
+            return R::from_err(ActorError::StashFull);
+        }
+
+        let Some(reply) = self.reply.take() else {
+            return R::from_err(ActorError::ReplyTaken);
+        };
+
+        let mut msg = Multi::new(Envelope::relay(msg, reply));
+        if let Some(deadline) = self.deadline {
+            msg = msg.expires_at(deadline);
+        }
+        self.ctx.stash.push_back(msg);
+        R::from_err(ActorError::Stashed)
+    }
 }
 
 impl<'a, A> Exec<'a, A>
@@ -57,6 +57,7 @@
 //! - [`Envelope`]: Type-safe message containers for request-response patterns
 //! - [`Handler`]: Trait for polymorphic message handling
 //! - [`Multi`]: Support for handling multiple message types in a single actor
+//! - [`ActorSystem`]: Groups actors under one root token for shutdown and statistics
 //!
 //! ## Examples
 //!
@@ -68,16 +69,29 @@
 //! - Worker pools
 
 mod actor;
+mod ask;
+mod autoscale;
+mod behavior;
 pub mod cancel;
 mod channel;
+mod children;
 pub mod count;
+mod crash;
 mod drop;
 mod envelope;
 mod error;
 mod handler;
 mod link;
+mod monitor;
 mod multi;
+mod overflow;
+mod passivation;
+mod permit;
 mod proxy;
+mod restart;
+mod router;
+mod spawn;
+mod system;
 mod weak;
 
 /// Common imports for working with the Actor12 framework.
@@ -95,18 +109,62 @@ pub mod prelude {
 pub use actor::Actor;
 pub use actor::ActorContext;
 pub use actor::Init;
+pub use actor::ReceiveTimeout;
+pub use actor::Terminate;
+pub use ask::Ask;
+pub use autoscale::AutoscalePolicy;
+pub use autoscale::AutoscalePool;
+pub use behavior::Behavior;
+pub use behavior::HandlerFn;
+pub use behavior::Unhandled;
 pub use channel::MpscChannel;
+pub use channel::Priority;
+pub use channel::PriorityChannel;
+pub use channel::SharedChannel;
+pub use channel::UnboundedChannel;
+pub use children::Children;
+pub use crash::Crash;
+pub use crash::CrashPolicy;
+pub use crash::HandlerPanic;
 pub use drop::DropHandle;
 pub use envelope::Envelope;
 pub use envelope::NoReply;
 pub use error::ActorError;
+pub use error::Full;
+pub use error::StashFull;
 pub use handler::Call;
 pub use handler::Exec;
 pub use handler::Handler;
 pub use link::DynLink;
 pub use link::Link;
+pub use monitor::ActorExit;
+pub use monitor::Down;
+pub use monitor::Monitor;
+pub use monitor::Shutdown;
 pub use multi::Multi;
+pub use overflow::Block;
+pub use overflow::DropNewest;
+pub use overflow::DropOldest;
+pub use overflow::Overflow;
+pub use overflow::OverflowChannel;
+pub use overflow::OverflowError;
+pub use overflow::OverflowPermit;
+pub use overflow::OverflowPolicy;
+pub use overflow::OverflowReceiver;
+pub use overflow::OverflowSender;
+pub use overflow::Reject;
+pub use overflow::Rejectable;
+pub use permit::Permit;
 pub use proxy::Proxy;
+pub use proxy::Strategy;
+pub use proxy::Supervisor;
+pub use proxy::SupervisorExit;
+pub use restart::RestartPolicy;
+pub use restart::Restarts;
+pub use router::Router;
+pub use router::Routing;
+pub use spawn::Spawner;
+pub use system::ActorSystem;
 pub use weak::WeakLink;
 
 /// Spawn a new actor instance with the given specification.
@@ -151,3 +209,18 @@ pub use weak::WeakLink;
 pub fn spawn<A: Actor>(spec: A::Spec) -> Link<A> {
     A::spawn(spec)
 }
+
+/// Spawn `size` instances of an actor sharing one mailbox, behind a single
+/// link.
+///
+/// A shorthand for `Spawner::new(spec).pool(size)`, see
+/// [`Spawner::pool`]. The actor's channel must be a
+/// [`SharedChannel`] (or another channel with a cloneable receiver).
+pub fn spawn_pool<A>(size: usize, spec: A::Spec) -> Link<A>
+where
+    A: Actor,
+    A::Spec: Clone,
+    <A::Channel as channel::ActorChannel>::Receiver: Clone,
+{
+    Spawner::new(spec).pool(size)
+}
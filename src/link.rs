@@ -36,9 +36,14 @@
 //! ## Lifecycle
 //!
 //! - [`alive`](Link::alive) reports whether the actor is still running.
-//! - [`wait`](Link::wait) yields a future that completes when it shuts down.
+//! - [`ready`](Link::ready) resolves once the actor's `init` has finished.
+//! - [`wait`](Link::wait) yields a future that completes when it shuts down;
+//!   [`join`](Link::join) also reports how it ended as an
+//!   [`ActorExit`](crate::ActorExit).
 //! - [`cancel`](Link::cancel) requests shutdown with a reason; the
-//!   [`cancel_and_wait`](Link::cancel_and_wait) variant also waits for it.
+//!   [`cancel_and_wait`](Link::cancel_and_wait) variant also waits for it,
+//!   and [`shutdown`](Link::shutdown) waits at most a grace period before
+//!   aborting the actor's task.
 //! - Dropping the last [`Link`] cancels the actor with the *default* cancel
 //!   reason via [`LinkState`]'s `Drop` impl.
 //!
@@ -98,7 +103,9 @@
 //! ```
 
 use std::fmt::Debug;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::cancel::CancelToken;
 use downcast_rs::DowncastSync;
@@ -106,19 +113,35 @@ use downcast_rs::impl_downcast;
 use futures::FutureExt;
 use futures::future::BoxFuture;
 use tokio::sync::OnceCell;
+use tokio::sync::oneshot;
+use tokio::sync::watch;
 use tokio::sync::oneshot::error::RecvError;
 use tokio::task::JoinHandle;
+use tokio::time::Instant;
 
 use crate::Call;
 use crate::actor::Actor;
+use crate::ask::Ask;
+use crate::ask::Request;
+use crate::ask::within;
 use crate::actor::SyncTrait;
 use crate::channel::ActorChannel;
 use crate::channel::ActorSender;
+use crate::channel::Priority;
+use crate::channel::TrySendError;
 use crate::envelope::Envelope;
+use crate::error::ActorError;
 use crate::error::ActorSendError;
 use crate::error::FromError;
+use crate::error::Full;
 use crate::handler::Handler;
+use crate::monitor::ActorExit;
+use crate::monitor::ExitSender;
+use crate::monitor::Shutdown;
+use crate::monitor::exited;
 use crate::multi::Multi;
+use crate::passivation::Parking;
+use crate::permit::Permit;
 
 /// The subset of an [`Actor`]'s associated types that a [`Link`] needs.
 ///
@@ -137,6 +160,11 @@ pub trait ActorLike: 'static + Send + Sync + Sized {
     type Channel: ActorChannel<Message = Self::Message>;
     /// User-facing state attached to the link and readable via [`Link::state`].
     type State: Send + Sync + 'static;
+
+    /// See [`Actor::ask_timeout`].
+    fn ask_timeout() -> Option<Duration> {
+        None
+    }
 }
 
 impl<A> ActorLike for A
@@ -147,6 +175,10 @@ where
     type Message = <Self as Actor>::Message;
     type Channel = <Self as Actor>::Channel;
     type State = <Self as Actor>::State;
+
+    fn ask_timeout() -> Option<Duration> {
+        <Self as Actor>::ask_timeout()
+    }
 }
 
 /// Shared inner state of a [`Link`], held behind an `Arc`.
@@ -164,6 +196,15 @@ pub struct LinkState<A: ActorLike> {
     pub monitor: OnceCell<JoinHandle<()>>,
     /// User-facing state snapshot, exposed through [`Link::state`].
     pub state: A::State,
+    /// Reports how the actor's task ended, see [`Link::monitor`].
+    pub(crate) exit: ExitSender<A::Cancel>,
+    /// Set once `init` has returned successfully, see [`Link::ready`].
+    pub(crate) ready: watch::Sender<bool>,
+    /// Whether messages record when they were sent, see [`Multi::timed`].
+    pub(crate) timed: bool,
+    /// Where the actor waits while passivated, see
+    /// [`Spawner::passivate_after`](crate::Spawner::passivate_after).
+    pub(crate) parking: OnceCell<Arc<Parking>>,
 }
 
 /// A cloneable, reference-counted handle to a running actor.
@@ -222,12 +263,30 @@ impl<A: ActorLike> Link<A> {
         tx: <A::Channel as ActorChannel>::Sender,
         token: CancelToken<A::Cancel>,
         state: A::State,
+    ) -> Self {
+        let link = Self::create(tx, token, state, false);
+        // No task runs `init` behind this link.
+        link.state.ready.send_replace(true);
+        link
+    }
+
+    /// Like [`new`](Self::new). With `timed`, every [`Multi`] message sent
+    /// through the link records when it was sent, see [`Multi::timed`].
+    pub(crate) fn create(
+        tx: <A::Channel as ActorChannel>::Sender,
+        token: CancelToken<A::Cancel>,
+        state: A::State,
+        timed: bool,
     ) -> Self {
         let state = Arc::new(LinkState {
             tx,
             token,
             monitor: Default::default(),
             state,
+            exit: ExitSender::new(None),
+            ready: watch::Sender::new(false),
+            timed,
+            parking: Default::default(),
         });
         Self { state }
     }
@@ -242,9 +301,44 @@ impl<A: ActorLike> Link<A> {
 
     /// Returns a future that completes when the actor shuts down.
     ///
-    /// Resolves immediately if the actor has already stopped.
+    /// Resolves once the actor's task has ended, after any
+    /// [`Terminate::ProcessAll`](crate::Terminate::ProcessAll) drain and its
+    /// [`terminate`](crate::Actor::terminate). Resolves immediately if the
+    /// actor has already stopped.
     pub fn wait(&self) -> BoxFuture<'_, ()> {
-        self.state.tx.closed().boxed()
+        self.state.stopped()
+    }
+
+    /// Waits for the actor's task to end and reports how it ended.
+    ///
+    /// Like [`wait`](Self::wait), but also tells a clean stop
+    /// ([`ActorExit::Completed`]) from a crash or a failed
+    /// [`init`](crate::Actor::init). Resolves immediately if the actor
+    /// has already stopped. A link without a task, as built by
+    /// [`new`](Self::new), reports [`ActorExit::Completed`] once its mailbox
+    /// closes.
+    pub fn join(&self) -> impl Future<Output = ActorExit<A::Cancel>> + Send + use<A> {
+        self.state.ended()
+    }
+
+    /// Waits for the actor's [`init`](crate::Actor::init) to finish.
+    ///
+    /// Resolves to `Ok(())` once `init` has returned successfully, or to the
+    /// actor's [`ActorExit`] if it stopped before that, e.g.
+    /// [`ActorExit::InitFailed`] with the error `init` returned. Messages sent
+    /// in the meantime are buffered in the mailbox as usual. A link without
+    /// a task, as built by [`new`](Self::new), has no `init` to wait for and
+    /// is ready from the start.
+    pub fn ready(&self) -> impl Future<Output = Result<(), ActorExit<A::Cancel>>> + Send + use<A> {
+        let mut ready = self.state.ready.subscribe();
+        let exit = self.state.ended();
+        async move {
+            tokio::select! {
+                biased;
+                Ok(_) = ready.wait_for(|ready| *ready) => Ok(()),
+                exit = exit => Err(exit),
+            }
+        }
     }
 
     /// Borrows the user-facing [`State`](crate::Actor::State) attached to this link.
@@ -276,21 +370,7 @@ impl<A: ActorLike> Link<A> {
         A: Handler<T>,
         A: ActorLike<Message = Multi<A>>,
     {
-        let (envelope, rx) = Envelope::<T, <A as Handler<T>>::Reply>::new(message);
-        match self.state.tx.send(Multi::new(envelope)).await {
-            Ok(()) => {}
-            Err(e) => {
-                return std::future::ready(<A as Handler<T>>::Reply::from_err(e)).boxed();
-            }
-        }
-
-        async move {
-            match rx.await {
-                Ok(response) => response,
-                Err(e) => <A as Handler<T>>::Reply::from_err(e),
-            }
-        }
-        .boxed()
+        self.ask(message).deferred().await
     }
 
     /// Fire-and-forget: sends a message without waiting for a reply.
@@ -337,7 +417,9 @@ impl<A: ActorLike> Link<A> {
         A: ActorLike<Message = Multi<A>>,
     {
         let (envelope, _) = Envelope::<T, <A as Handler<T>>::Reply>::new(message);
-        if let Ok(()) = self.state.tx.send(Multi::new(envelope)).await {}
+        if let Ok(()) = self.state.tx.send(self.state.wrap(envelope)).await {
+            self.state.wake();
+        }
     }
 
     /// Forwards a pre-built [`Envelope`] to the actor.
@@ -355,7 +437,9 @@ impl<A: ActorLike> Link<A> {
         A: Handler<T>,
         A: ActorLike<Message = Multi<A>>,
     {
-        let _ = self.state.tx.send(Multi::new(envelope)).await;
+        if let Ok(()) = self.state.tx.send(self.state.wrap(envelope)).await {
+            self.state.wake();
+        }
     }
 
     /// Sends a message and awaits the actor's typed reply.
@@ -407,17 +491,131 @@ impl<A: ActorLike> Link<A> {
         A: Handler<T>,
         A: ActorLike<Message = Multi<A>>,
     {
-        let (envelope, rx) = Envelope::<T, <A as Handler<T>>::Reply>::new(message);
-        match self.state.tx.send(Multi::new(envelope)).await {
-            Ok(()) => {}
-            Err(e) => {
-                return <A as Handler<T>>::Reply::from_err(e);
+        let reply = async {
+            match self.post(message).await {
+                Ok(rx) => reply(rx).await,
+                Err(reply) => reply,
+            }
+        };
+        within(A::ask_timeout(), reply).await
+    }
+
+    /// Builds a request to the actor, see [`Ask`].
+    ///
+    /// Awaiting the request behaves like [`ask_dyn`](Self::ask_dyn), and
+    /// [`Ask::deferred`] like [`ask_dyn_async`](Self::ask_dyn_async), but the
+    /// timeout can be set per request.
+    pub fn ask<T>(&self, message: T) -> Ask<'_, <A as Handler<T>>::Reply>
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let request = async move { self.post(message).await.map(|rx| reply(rx).boxed()) };
+        Ask::new(request.boxed(), A::ask_timeout())
+    }
+
+    /// Like [`ask_dyn`](Self::ask_dyn), with the message queued at
+    /// `priority` (see [`PriorityChannel`](crate::PriorityChannel)).
+    pub async fn ask_dyn_with_priority<T>(&self, message: T, priority: Priority) -> <A as Handler<T>>::Reply
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let reply = async {
+            match self.post_with_priority(message, priority, None).await {
+                Ok(rx) => reply(rx).await,
+                Err(reply) => reply,
             }
+        };
+        within(A::ask_timeout(), reply).await
+    }
+
+    /// Like [`tell_dyn`](Self::tell_dyn), with the message queued at
+    /// `priority` (see [`PriorityChannel`](crate::PriorityChannel)).
+    pub async fn tell_dyn_with_priority<T>(&self, message: T, priority: Priority)
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let (envelope, _) = Envelope::<T, <A as Handler<T>>::Reply>::new(message);
+        if let Ok(()) = self.state.tx.send_with_priority(self.state.wrap(envelope), priority).await {
+            self.state.wake();
         }
+    }
+
+    /// Like [`ask_dyn`](Self::ask_dyn), but the actor skips the message if
+    /// it is still queued once `ttl` has passed. The reply is then
+    /// [`ActorError::Expired`] converted into the reply type.
+    ///
+    /// The TTL only covers the wait in the mailbox; the reply is still
+    /// awaited for up to [`Actor::ask_timeout`](crate::Actor::ask_timeout).
+    pub async fn ask_dyn_with_ttl<T>(&self, message: T, ttl: Duration) -> <A as Handler<T>>::Reply
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        // A TTL too long to represent as an instant never expires.
+        let deadline = Instant::now().checked_add(ttl);
+        let reply = async {
+            match self.post_with_priority(message, Priority::NORMAL, deadline).await {
+                Ok(rx) => reply(rx).await,
+                Err(reply) => reply,
+            }
+        };
+        within(A::ask_timeout(), reply).await
+    }
+
+    /// Like [`tell_dyn`](Self::tell_dyn), but the actor skips the message if
+    /// it is still queued once `ttl` has passed.
+    pub async fn tell_dyn_with_ttl<T>(&self, message: T, ttl: Duration)
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let deadline = Instant::now().checked_add(ttl);
+        let _ = self.post_with_priority(message, Priority::NORMAL, deadline).await;
+    }
+
+    /// Sends `message` and returns the receiver of its reply.
+    pub(crate) async fn post<T>(
+        &self,
+        message: T,
+    ) -> Result<oneshot::Receiver<<A as Handler<T>>::Reply>, <A as Handler<T>>::Reply>
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        self.post_with_priority(message, Priority::NORMAL, None).await
+    }
 
-        match rx.await {
-            Ok(response) => response,
-            Err(e) => <A as Handler<T>>::Reply::from_err(e),
+    async fn post_with_priority<T>(
+        &self,
+        message: T,
+        priority: Priority,
+        deadline: Option<Instant>,
+    ) -> Result<oneshot::Receiver<<A as Handler<T>>::Reply>, <A as Handler<T>>::Reply>
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let (envelope, rx) = Envelope::<T, <A as Handler<T>>::Reply>::new(message);
+        let mut multi = self.state.wrap(envelope);
+        if let Some(deadline) = deadline {
+            multi = multi.expires_at(deadline);
+        }
+        match self.state.tx.send_with_priority(multi, priority).await {
+            Ok(()) => {
+                self.state.wake();
+                Ok(rx)
+            }
+            Err(e) => Err(<A as Handler<T>>::Reply::from_err(e)),
         }
     }
 
@@ -427,7 +625,8 @@ impl<A: ActorLike> Link<A> {
     /// message type is a plain [`Envelope<T, R>`](crate::Envelope) rather than
     /// [`Multi<A>`](crate::Multi). It wraps `message` in an envelope, sends it,
     /// and returns the reply `R`. Send/receive failures are converted into `R`
-    /// through `FromError`.
+    /// through `FromError`. It waits for the reply however long it takes; use
+    /// [`request`](Self::request) to apply a timeout.
     ///
     /// # Examples
     ///
@@ -464,18 +663,152 @@ impl<A: ActorLike> Link<A> {
         R: Send + Sync + 'static,
         R: FromError<ActorSendError<A>>,
         R: FromError<RecvError>,
+    {
+        match self.post_envelope(message).await {
+            Ok(rx) => reply(rx).await,
+            Err(reply) => reply,
+        }
+    }
+
+    /// Builds a request to a single-message actor, see [`Ask`].
+    ///
+    /// Awaiting the request behaves like [`send`](Self::send), but the
+    /// timeout can be set per request.
+    pub fn request<T, R>(&self, message: T) -> Ask<'_, R>
+    where
+        A: ActorLike<Message = Envelope<T, R>>,
+        T: Send + Sync + 'static,
+        R: Send + Sync + 'static,
+        R: FromError<ActorSendError<A>>,
+        R: FromError<RecvError>,
+        R: FromError<ActorError>,
+    {
+        let request = async move {
+            self.post_envelope(message)
+                .await
+                .map(|rx| reply(rx).boxed())
+        };
+        Ask::new(request.boxed(), A::ask_timeout())
+    }
+
+    /// Like [`post`](Self::post), for single-message actors.
+    pub(crate) async fn post_envelope<T, R>(&self, message: T) -> Result<oneshot::Receiver<R>, R>
+    where
+        A: ActorLike<Message = Envelope<T, R>>,
+        T: Send + Sync + 'static,
+        R: Send + Sync + 'static,
+        R: FromError<ActorSendError<A>>,
     {
         let (envelope, rx) = Envelope::<T, R>::new(message);
         match self.state.tx.send(envelope).await {
-            Ok(()) => {}
-            Err(e) => {
-                return R::from_err(e);
+            Ok(()) => {
+                self.state.wake();
+                Ok(rx)
             }
+            Err(e) => Err(R::from_err(e)),
         }
+    }
+
+    /// Reserves a slot in the mailbox, waiting for one to free up if
+    /// needed.
+    ///
+    /// Reserve before doing expensive work to build a message, so the work
+    /// is only done once the actor can take it. Fails with
+    /// [`ActorError::Dead`] once the mailbox is closed.
+    pub async fn reserve(&self) -> Result<Permit<A>, ActorError> {
+        match self.state.tx.reserve().await {
+            Some(permit) => Ok(Permit::new(permit, self.state.clone())),
+            None => Err(ActorError::Dead),
+        }
+    }
+
+    /// Number of messages the mailbox can take right now without waiting.
+    pub fn capacity(&self) -> usize {
+        self.state.tx.capacity()
+    }
+
+    /// Number of messages the empty mailbox can take, see
+    /// [`Actor::mailbox_capacity`].
+    pub fn max_capacity(&self) -> usize {
+        self.state.tx.max_capacity()
+    }
+
+    /// Number of messages waiting in the mailbox, counting slots held by
+    /// unused [`Permit`]s.
+    pub fn len(&self) -> usize {
+        self.max_capacity() - self.capacity()
+    }
 
-        match rx.await {
-            Ok(response) => response,
-            Err(e) => R::from_err(e),
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of messages the mailbox has dropped on overflow, see
+    /// [`OverflowChannel`](crate::OverflowChannel).
+    pub fn dropped(&self) -> u64 {
+        self.state.tx.dropped()
+    }
+
+    /// Like [`tell_dyn`](Self::tell_dyn), but never waits for room in the
+    /// mailbox: if it is full the message is handed back in [`Full`].
+    pub fn try_tell_dyn<T>(&self, message: T) -> Result<(), Full<T>>
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let (envelope, _) = Envelope::<T, <A as Handler<T>>::Reply>::new(message);
+        match self.state.tx.try_send(self.state.wrap(envelope)) {
+            Ok(()) => {
+                self.state.wake();
+                Ok(())
+            }
+            Err(TrySendError::Full(msg)) => Err(Full(unwrap_multi(msg))),
+            Err(TrySendError::Closed(_)) => Ok(()),
+        }
+    }
+
+    /// Like [`ask_dyn_async`](Self::ask_dyn_async), but never waits for room
+    /// in the mailbox: if it is full the message is handed back in [`Full`].
+    pub fn try_ask_dyn<T>(&self, message: T) -> Result<BoxFuture<'static, <A as Handler<T>>::Reply>, Full<T>>
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let (envelope, rx) = Envelope::<T, <A as Handler<T>>::Reply>::new(message);
+        match self.state.tx.try_send(self.state.wrap(envelope)) {
+            Ok(()) => {
+                self.state.wake();
+                Ok(reply_within(rx, A::ask_timeout()))
+            }
+            Err(TrySendError::Full(msg)) => Err(Full(unwrap_multi(msg))),
+            Err(TrySendError::Closed(e)) => {
+                Ok(std::future::ready(<A as Handler<T>>::Reply::from_err(e)).boxed())
+            }
+        }
+    }
+
+    /// Like [`send`](Self::send), but never waits for room in the mailbox:
+    /// if it is full the message is handed back in [`Full`]. Otherwise
+    /// returns the future of the reply.
+    pub fn try_send<T, R>(&self, message: T) -> Result<BoxFuture<'static, R>, Full<T>>
+    where
+        A: ActorLike<Message = Envelope<T, R>>,
+        T: Send + Sync + 'static,
+        R: Send + Sync + 'static,
+        R: FromError<ActorSendError<A>>,
+        R: FromError<RecvError>,
+        R: FromError<ActorError>,
+    {
+        let (envelope, rx) = Envelope::<T, R>::new(message);
+        match self.state.tx.try_send(envelope) {
+            Ok(()) => {
+                self.state.wake();
+                Ok(reply_within(rx, A::ask_timeout()))
+            }
+            Err(TrySendError::Full(envelope)) => Err(Full(envelope.value)),
+            Err(TrySendError::Closed(e)) => Ok(std::future::ready(R::from_err(e)).boxed()),
         }
     }
 
@@ -486,7 +819,7 @@ impl<A: ActorLike> Link<A> {
     /// [`ask_dyn`](Self::ask_dyn)/[`tell_dyn`](Self::tell_dyn)/[`send`](Self::send)
     /// unless you need this control.
     pub async fn send_raw(&self, message: A::Message) -> Result<(), ActorSendError<A>> {
-        self.state.tx.send(message).await
+        self.state.tx.send(message).await.inspect(|()| self.state.wake())
     }
 
     /// Requests that the actor shut down, with the given reason.
@@ -520,10 +853,22 @@ impl<A: ActorLike> Link<A> {
     /// Requests shutdown and awaits the actor's termination.
     ///
     /// Like [`cancel`](Self::cancel), but the returned future resolves only once
-    /// the actor's mailbox has closed.
+    /// the actor's task has ended, as with [`wait`](Self::wait).
     pub async fn cancel_and_wait(&self, reason: A::Cancel) {
         self.state.token.cancel(reason);
-        self.state.tx.closed().await
+        self.state.stopped().await
+    }
+
+    /// Requests shutdown and waits at most `grace` for the actor to finish.
+    ///
+    /// If the actor (including its [`terminate`](crate::Actor::terminate)) is
+    /// still running when the grace period expires, its task is aborted and
+    /// [`join`](Self::join) reports [`ActorExit::Aborted`]. A link without a
+    /// task, as built by [`new`](Self::new), stops once its mailbox closes;
+    /// if that takes longer than `grace` it is left as it is and reported
+    /// as [`Shutdown::Forced`].
+    pub async fn shutdown(&self, reason: A::Cancel, grace: Duration) -> Shutdown {
+        self.state.stop_within(reason, grace).await
     }
 
     /// Erases the actor type, producing a [`DynLink<M>`] keyed only by message `M`.
@@ -546,6 +891,77 @@ impl<A: ActorLike> Link<A> {
     }
 }
 
+impl<A: ActorLike> LinkState<A> {
+    /// Waits for the actor's task to report its exit. A link without a task,
+    /// as built by [`Link::new`], stops when its mailbox closes.
+    fn stopped(&self) -> BoxFuture<'_, ()> {
+        match self.monitor.get() {
+            Some(_) => exited(self.exit.subscribe()).map(drop).boxed(),
+            None => self.tx.closed().boxed(),
+        }
+    }
+
+    /// Resolves with how the actor ended. A link without a task reports a
+    /// clean stop once its mailbox closes.
+    pub(crate) fn ended(&self) -> BoxFuture<'static, ActorExit<A::Cancel>> {
+        match self.monitor.get() {
+            Some(_) => exited(self.exit.subscribe()).boxed(),
+            None => {
+                let tx = self.tx.clone();
+                let token = self.token.clone();
+                async move {
+                    tx.closed().await;
+                    ActorExit::Completed(token.reason().unwrap_or_default())
+                }
+                .boxed()
+            }
+        }
+    }
+
+    /// Wakes the actor if it is passivated, once a message has been queued.
+    pub(crate) fn wake(&self) {
+        if let Some(parking) = self.parking.get() {
+            parking.wake();
+        }
+    }
+
+    /// Wraps `envelope` for the mailbox, see [`Multi::timed`].
+    pub(crate) fn wrap<T>(&self, envelope: Envelope<T, <A as Handler<T>>::Reply>) -> Multi<A>
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+    {
+        let multi = Multi::new(envelope);
+        if self.timed { multi.timed() } else { multi }
+    }
+
+    async fn stop_within(&self, reason: A::Cancel, grace: Duration) -> Shutdown {
+        self.token.cancel(reason);
+
+        if tokio::time::timeout(grace, self.stopped()).await.is_ok() {
+            return Shutdown::Graceful;
+        }
+
+        // A link without a task has nothing to abort.
+        if let Some(handle) = self.monitor.get() {
+            handle.abort();
+            if let Some(parking) = self.parking.get() {
+                parking.abort();
+            }
+
+            // The task may have finished on its own in the meantime.
+            self.exit.send_if_modified(|exit| match exit {
+                Some(_) => false,
+                None => {
+                    *exit = Some(ActorExit::Aborted);
+                    true
+                }
+            });
+        }
+        Shutdown::Forced
+    }
+}
+
 /// Dropping the last reference to the shared state cancels the actor with the
 /// default [`Cancel`](crate::Actor::Cancel) reason, tying the actor's lifetime to
 /// its links.
@@ -555,6 +971,34 @@ impl<A: ActorLike> Drop for LinkState<A> {
     }
 }
 
+/// Waits for the reply to a request.
+pub(crate) async fn reply<R: FromError<RecvError>>(rx: oneshot::Receiver<R>) -> R {
+    match rx.await {
+        Ok(response) => response,
+        Err(e) => R::from_err(e),
+    }
+}
+
+/// Waits for the reply to a request for at most `timeout`.
+fn reply_within<R>(rx: oneshot::Receiver<R>, timeout: Option<Duration>) -> BoxFuture<'static, R>
+where
+    R: FromError<RecvError> + FromError<ActorError> + Send + 'static,
+{
+    within(timeout, reply(rx)).boxed()
+}
+
+/// Recovers a message handed back by the channel.
+fn unwrap_multi<A, T>(msg: Multi<A>) -> T
+where
+    T: SyncTrait,
+    A: Handler<T>,
+{
+    match msg.downcast::<T>() {
+        Ok(envelope) => envelope.value,
+        Err(_) => unreachable!("the message was sent as {}", std::any::type_name::<T>()),
+    }
+}
+
 /// Object-safe interface backing a type-erased [`DynLink`].
 ///
 /// [`LinkState<A>`] implements this for every message type `M` the actor
@@ -568,6 +1012,12 @@ pub trait DynamicLink<T>: DowncastSync {
     fn tell_dyn(&self, message: T) -> BoxFuture<'_, ()>;
     /// Cancels the actor and waits for it to terminate.
     fn cancel_and_wait(&'_ self) -> BoxFuture<'_, ()>;
+    /// Cancels the actor and aborts it if it hasn't stopped within `grace`.
+    fn shutdown(&self, grace: Duration) -> BoxFuture<'_, Shutdown>;
+    /// Sends `message`, resolving once the actor has handled it.
+    fn ask(&self, message: T) -> Request<'_, anyhow::Result<()>>;
+    /// The actor's [`Actor::ask_timeout`].
+    fn ask_timeout(&self) -> Option<Duration>;
 }
 
 impl_downcast!(sync DynamicLink<M>);
@@ -606,6 +1056,20 @@ impl<M: Send + Sync + 'static> DynLink<M> {
         self.state.cancel_and_wait()
     }
 
+    /// Cancels the actor and aborts it if it hasn't stopped within `grace`.
+    pub fn shutdown(&self, grace: Duration) -> BoxFuture<'_, Shutdown> {
+        self.state.shutdown(grace)
+    }
+
+    /// Builds a request to the erased actor, see [`Ask`].
+    ///
+    /// The reply type is erased along with the actor, so the request resolves
+    /// to `Ok(())` once the actor has handled the message, and the reply
+    /// itself is discarded.
+    pub fn ask(&self, message: M) -> Ask<'_, anyhow::Result<()>> {
+        Ask::new(self.state.ask(message), self.state.ask_timeout())
+    }
+
     /// Recovers the concrete [`Link<A>`] from this erased link.
     ///
     /// # Panics
@@ -639,12 +1103,41 @@ where
 
     fn tell_dyn(&self, message: M) -> BoxFuture<'_, ()> {
         let (envelope, _) = Envelope::<M, <A as Handler<M>>::Reply>::new(message);
-        self.tx.send(Multi::new(envelope)).map(|_| ()).boxed()
+        self.tx
+            .send(self.wrap(envelope))
+            .map(|sent| {
+                if sent.is_ok() {
+                    self.wake();
+                }
+            })
+            .boxed()
     }
 
     fn cancel_and_wait(&'_ self) -> BoxFuture<'_, ()> {
         <LinkState<A> as DynamicLink<M>>::cancel(self);
-        self.tx.closed().boxed()
+        self.stopped()
+    }
+
+    fn shutdown(&self, grace: Duration) -> BoxFuture<'_, Shutdown> {
+        self.stop_within(Default::default(), grace).boxed()
+    }
+
+    fn ask(&self, message: M) -> Request<'_, anyhow::Result<()>> {
+        let (envelope, rx) = Envelope::<M, <A as Handler<M>>::Reply>::new(message);
+        async move {
+            match self.tx.send(self.wrap(envelope)).await {
+                Ok(()) => {
+                    self.wake();
+                    Ok(rx.map(|reply| reply.map(drop).map_err(Into::into)).boxed())
+                }
+                Err(_) => Err(anyhow::Result::from_err(ActorError::Dead)),
+            }
+        }
+        .boxed()
+    }
+
+    fn ask_timeout(&self) -> Option<Duration> {
+        A::ask_timeout()
     }
 }
 
@@ -0,0 +1,138 @@
+use std::future::Future;
+
+use tokio::sync::watch;
+use tokio::task::AbortHandle;
+
+use crate::actor::Actor;
+use crate::cancel::CancelReason;
+use crate::channel::Priority;
+use crate::crash::Crash;
+use crate::handler::Handler;
+use crate::link::ActorLike;
+use crate::link::Link;
+use crate::multi::Multi;
+use crate::weak::WeakLink;
+
+/// How an actor's task ended.
+#[derive(Debug, Clone)]
+pub enum ActorExit<C> {
+    /// The actor ran its `terminate` after being cancelled with this reason
+    /// (including the default reason used when its last link is dropped).
+    Completed(CancelReason<C>),
+    /// The actor panicked and its [`CrashPolicy`](crate::CrashPolicy) did not
+    /// bring it back.
+    Crashed(Crash),
+    /// [`Actor::init`](crate::Actor::init) returned this error.
+    InitFailed(C),
+    /// [`Actor::init`](crate::Actor::init) did not finish within the
+    /// spawner's [`init_timeout`](crate::Spawner::init_timeout).
+    InitTimedOut,
+    /// The task went away without reporting, e.g. it was aborted or the
+    /// runtime shut down.
+    Aborted,
+}
+
+impl<C> ActorExit<C> {
+    /// `true` if the actor was stopped through cancellation rather than by
+    /// failing.
+    pub fn is_clean(&self) -> bool {
+        matches!(self, ActorExit::Completed(_))
+    }
+}
+
+/// Outcome of [`Link::shutdown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shutdown {
+    /// The actor stopped within the grace period.
+    Graceful,
+    /// The grace period expired and the actor's task was aborted.
+    Forced,
+}
+
+pub(crate) type ExitSender<C> = watch::Sender<Option<ActorExit<C>>>;
+pub(crate) type ExitReceiver<C> = watch::Receiver<Option<ActorExit<C>>>;
+
+/// Waits for the exit reported through `exit`.
+pub(crate) async fn exited<C: Clone>(mut exit: ExitReceiver<C>) -> ActorExit<C> {
+    match exit.wait_for(Option::is_some).await {
+        Ok(exit) => exit.clone().unwrap_or(ActorExit::Aborted),
+        Err(_) => ActorExit::Aborted,
+    }
+}
+
+/// Sent to a watching actor when an actor it monitors terminates.
+///
+/// See [`Link::monitor`](crate::Link::monitor). `link` identifies the actor
+/// that went down; it compares equal to the weak link of that actor but can no
+/// longer be upgraded. A watcher with a [`PriorityChannel`](crate::PriorityChannel)
+/// receives it ahead of every user message, even when its mailbox is full.
+pub struct Down<A: ActorLike> {
+    pub link: WeakLink<A>,
+    pub exit: ActorExit<A::Cancel>,
+}
+
+/// Handle to an installed monitor.
+///
+/// Dropping it leaves the monitor in place; call
+/// [`demonitor`](Self::demonitor) to stop watching.
+#[derive(Debug)]
+pub struct Monitor {
+    pub(crate) handle: AbortHandle,
+}
+
+impl Monitor {
+    /// Stops watching; no [`Down`] is delivered afterwards.
+    pub fn demonitor(self) {
+        self.handle.abort();
+    }
+}
+
+impl<A: Actor> Link<A> {
+    /// Watches this actor from `watcher`.
+    ///
+    /// When this actor terminates, `watcher` receives a [`Down<A>`] through its
+    /// own [`Handler`], carrying this actor's identity and [`ActorExit`]. The
+    /// watcher does not keep this actor alive. The monitor goes away on its
+    /// own once either side has terminated.
+    pub fn monitor<W>(&self, watcher: &WeakLink<W>) -> Monitor
+    where
+        W: Actor + Handler<Down<A>> + ActorLike<Message = Multi<W>>,
+    {
+        install(self.downgrade(), self.join(), watcher)
+    }
+}
+
+impl<A: Actor> WeakLink<A> {
+    /// Like [`Link::monitor`]. If the actor is already gone, the [`Down`] is
+    /// delivered right away with the exit it reported.
+    pub fn monitor<W>(&self, watcher: &WeakLink<W>) -> Monitor
+    where
+        W: Actor + Handler<Down<A>> + ActorLike<Message = Multi<W>>,
+    {
+        install(self.clone(), self.join(), watcher)
+    }
+}
+
+fn install<A, W>(
+    target: WeakLink<A>,
+    exit: impl Future<Output = ActorExit<A::Cancel>> + Send + 'static,
+    watcher: &WeakLink<W>,
+) -> Monitor
+where
+    A: Actor,
+    W: Actor + Handler<Down<A>> + ActorLike<Message = Multi<W>>,
+{
+    let watcher = watcher.clone();
+    let handle = tokio::spawn(async move {
+        tokio::select! {
+            exit = exit => if let Some(watcher) = watcher.upgrade() {
+                watcher.tell_dyn_with_priority(Down { link: target, exit }, Priority::SYSTEM).await;
+            },
+            _ = exited(watcher.exit.clone()) => {}
+        }
+    });
+
+    Monitor {
+        handle: handle.abort_handle(),
+    }
+}
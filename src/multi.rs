@@ -1,25 +1,63 @@
+use std::any::TypeId;
+use std::any::type_name;
 use std::future::Future;
+use std::panic::AssertUnwindSafe;
 
+use downcast_rs::DowncastSync;
+use downcast_rs::impl_downcast;
 use futures::FutureExt as _;
 use futures::future::BoxFuture;
 use take_once::TakeOnce;
+use tokio::time::Instant;
 
 use crate::actor::ActorMessage;
+use crate::behavior::HandlerFn;
 use crate::actor::SyncTrait;
+use crate::crash::isolated;
+use crate::crash::panic_message;
 use crate::envelope::Envelope;
+use crate::error::ActorError;
+use crate::error::FromError;
 use crate::handler::Call;
 use crate::handler::Exec;
 use crate::handler::Handler;
 use crate::link::ActorLike;
 
-pub trait MultiHandler<A>
+pub trait MultiHandler<A>: DowncastSync
 where
 	Self: Send + Sync + 'static,
 	A: ActorLike,
 {
 	fn handle<'a>(self: Box<Self>, state: &'a mut A, ctx: Exec<'a, A>) -> BoxFuture<'a, ()>;
+
+	/// Answer the sender with `err` instead of handling the message.
+	fn reject(self: Box<Self>, _err: ActorError) {}
+
+	/// Name of the message type, for diagnostics.
+	fn message_name(&self) -> &'static str {
+		type_name::<Self>()
+	}
+
+	/// See [`Multi::expires_at`].
+	fn deadline(&self) -> Option<Instant> {
+		None
+	}
+
+	/// See [`Multi::expires_at`]. Handlers that don't track a deadline
+	/// ignore it.
+	fn set_deadline(&mut self, _deadline: Instant) {}
+
+	/// When the message was sent, if the handler keeps track.
+	fn sent_at(&self) -> Option<Instant> {
+		None
+	}
+
+	/// See [`Multi::timed`]. Handlers that don't keep track ignore it.
+	fn set_sent_at(&mut self, _sent: Instant) {}
 }
 
+impl_downcast!(sync MultiHandler<A> where A: ActorLike);
+
 pub struct Multi<A>
 where
 	A: ActorLike,
@@ -28,17 +66,36 @@ where
 }
 
 impl<A: ActorLike> ActorMessage<A> for Multi<A> {
+	fn unless_expired(self) -> Option<Self> {
+		match self.handler.deadline() {
+			Some(deadline) if deadline <= Instant::now() => {
+				tracing::debug!("Skipping expired {}", self.handler.message_name());
+				self.handler.reject(ActorError::Expired);
+				None
+			}
+			_ => Some(self),
+		}
+	}
+
 	fn handle<'a>(
 		self,
 		state: &'a mut A,
 		ctx: Exec<'a, A>,
 	) -> impl Future<Output = ()> + Send + 'a {
-		self.handler.handle(state, ctx)
+		if let (Some(queue_time), Some(sent)) = (&ctx.ctx.queue_time, self.handler.sent_at()) {
+			queue_time.record(sent);
+		}
+		match ctx.ctx.behavior.clone() {
+			Some(behavior) => behavior.dispatch(self, state, ctx),
+			None => self.handler.handle(state, ctx),
+		}
 	}
 }
 
 struct MultiEnvelope<M: SyncTrait, A: Handler<M>> {
 	pub envelope: Envelope<M, <A as Handler<M>>::Reply>,
+	pub deadline: Option<Instant>,
+	pub sent: Option<Instant>,
 }
 
 impl<A: ActorLike> Multi<A> {
@@ -46,20 +103,88 @@ impl<A: ActorLike> Multi<A> {
 	where
 		A: Handler<M>,
 	{
-		let runner = MultiEnvelope::<M, A> { envelope };
+		let runner = MultiEnvelope::<M, A> {
+			envelope,
+			deadline: None,
+			sent: None,
+		};
 		Multi {
 			handler: Box::new(runner),
 		}
 	}
-}
 
-impl<A, M> MultiHandler<A> for MultiEnvelope<M, A>
-where
-	M: SyncTrait,
-	A: Handler<M>,
-{
-	fn handle<'a>(self: Box<Self>, state: &'a mut A, ctx: Exec<'a, A>) -> BoxFuture<'a, ()> {
-		let (msg, reply) = self.envelope.split();
+	/// Skip the message if it is still in the mailbox at `deadline`; its
+	/// sender gets [`ActorError::Expired`] instead of a reply.
+	pub fn expires_at(mut self, deadline: Instant) -> Self {
+		self.handler.set_deadline(deadline);
+		self
+	}
+
+	/// Record that the message is sent now, so the receiving actor can tell
+	/// how long it waited in the mailbox. Only links of actors that report
+	/// queue times, such as [`AutoscalePool`](crate::AutoscalePool)
+	/// instances, pay for reading the clock.
+	pub(crate) fn timed(mut self) -> Self {
+		self.handler.set_sent_at(Instant::now());
+		self
+	}
+
+	/// `true` if this is an `M` message.
+	pub fn is<M: SyncTrait>(&self) -> bool
+	where
+		A: Handler<M>,
+	{
+		self.handler.is::<MultiEnvelope<M, A>>()
+	}
+
+	/// The `M` message, if this is one.
+	pub fn downcast_ref<M: SyncTrait>(&self) -> Option<&M>
+	where
+		A: Handler<M>,
+	{
+		self.handler
+			.downcast_ref::<MultiEnvelope<M, A>>()
+			.map(|runner| &runner.envelope.value)
+	}
+
+	/// Recover the envelope of an `M` message, or get the message back if it
+	/// is of another type.
+	pub fn downcast<M: SyncTrait>(self) -> Result<Envelope<M, <A as Handler<M>>::Reply>, Self>
+	where
+		A: Handler<M>,
+	{
+		match self.handler.downcast::<MultiEnvelope<M, A>>() {
+			Ok(runner) => Ok(runner.envelope),
+			Err(handler) => Err(Multi { handler }),
+		}
+	}
+
+	/// Identifies `M` messages, see [`key`](Self::key).
+	pub(crate) fn key_of<M: SyncTrait>() -> TypeId
+	where
+		A: Handler<M>,
+	{
+		TypeId::of::<MultiEnvelope<M, A>>()
+	}
+
+	/// Identifies the message type of this message.
+	pub(crate) fn key(&self) -> TypeId {
+		(*self.handler).as_any().type_id()
+	}
+
+	/// Handle an `M` message with `handler`: `A`'s [`Handler`] impl, or a
+	/// [`HandlerFn`] that replaces it.
+	pub(crate) fn handle_with<'a, M: SyncTrait>(
+		envelope: Envelope<M, <A as Handler<M>>::Reply>,
+		deadline: Option<Instant>,
+		state: &'a mut A,
+		ctx: Exec<'a, A>,
+		handler: impl HandleMessage<A, M> + Send + 'a,
+	) -> BoxFuture<'a, ()>
+	where
+		A: Handler<M>,
+	{
+		let (msg, reply) = envelope.split();
 
 		async move {
 			// `once` lives on the wrapper future's stack; `Call` borrows it,
@@ -67,22 +192,122 @@ where
 			let once = TakeOnce::new();
 			let _ = once.store(reply);
 
+			let isolate = ctx.ctx.isolate;
 			let value = {
 				let context = Call {
 					// Reborrow the actor context to the (shorter) lifetime of `once`.
 					ctx: Exec { ctx: &mut *ctx.ctx },
 					reply: &once,
+					deadline,
 				};
-				Handler::<M>::handle(&mut *state, context, msg).await
+				let handled = handler.call(&mut *state, context, msg);
+				match isolate {
+					Some(_) => AssertUnwindSafe(handled).catch_unwind().await,
+					None => Ok(handled.await),
+				}
 			};
 
-			// If the handler took the sender (manual `take_reply` or `reply_async`),
-			// `once` is empty and delivery is the handler's responsibility.
-			// Otherwise deliver the returned value now.
-			if let Some(tx) = once.take() {
-				let _ = tx.send(value);
+			if let (Err(payload), Some(isolate)) = (deliver::<A, M>(&once, value), isolate) {
+				isolated(state, ctx.ctx, isolate, payload);
 			}
 		}
 		.boxed()
 	}
 }
+
+/// Runs an `M` message for [`Multi::handle_with`].
+pub(crate) trait HandleMessage<A: Handler<M>, M: SyncTrait> {
+	fn call<'a>(
+		self,
+		state: &'a mut A,
+		ctx: Call<'a, A, <A as Handler<M>>::Reply>,
+		msg: M,
+	) -> impl Future<Output = <A as Handler<M>>::Reply> + Send + 'a;
+}
+
+/// `A`'s own [`Handler`] impl.
+struct OwnHandler;
+
+impl<A: Handler<M>, M: SyncTrait> HandleMessage<A, M> for OwnHandler {
+	fn call<'a>(
+		self,
+		state: &'a mut A,
+		ctx: Call<'a, A, <A as Handler<M>>::Reply>,
+		msg: M,
+	) -> impl Future<Output = <A as Handler<M>>::Reply> + Send + 'a {
+		Handler::<M>::handle(state, ctx, msg)
+	}
+}
+
+impl<A: Handler<M>, M: SyncTrait> HandleMessage<A, M> for HandlerFn<A, M> {
+	fn call<'a>(
+		self,
+		state: &'a mut A,
+		ctx: Call<'a, A, <A as Handler<M>>::Reply>,
+		msg: M,
+	) -> impl Future<Output = <A as Handler<M>>::Reply> + Send + 'a {
+		self(state, ctx, msg)
+	}
+}
+
+/// Send the handler's result unless the handler took the reply itself. A
+/// caught panic is answered with [`ActorError::HandlerPanicked`] and handed
+/// back.
+fn deliver<A: Handler<M>, M: SyncTrait>(
+	once: &TakeOnce<tokio::sync::oneshot::Sender<<A as Handler<M>>::Reply>>,
+	value: std::thread::Result<<A as Handler<M>>::Reply>,
+) -> std::thread::Result<()> {
+	let value = match value {
+		Ok(value) => value,
+		Err(payload) => {
+			// Answer the caller before the panic goes on to the actor.
+			if let Some(tx) = once.take() {
+				let err = ActorError::HandlerPanicked(panic_message(&*payload));
+				let _ = tx.send(<A as Handler<M>>::Reply::from_err(err));
+			}
+			return Err(payload);
+		}
+	};
+
+	// If the handler took the sender (manual `take_reply` or `reply_async`),
+	// `once` is empty and delivery is the handler's responsibility.
+	// Otherwise deliver the returned value now.
+	if let Some(tx) = once.take() {
+		let _ = tx.send(value);
+	}
+	Ok(())
+}
+
+impl<A, M> MultiHandler<A> for MultiEnvelope<M, A>
+where
+	M: SyncTrait,
+	A: Handler<M>,
+{
+	fn handle<'a>(self: Box<Self>, state: &'a mut A, ctx: Exec<'a, A>) -> BoxFuture<'a, ()> {
+		Multi::handle_with(self.envelope, self.deadline, state, ctx, OwnHandler)
+	}
+
+	fn reject(self: Box<Self>, err: ActorError) {
+		let _ = self.envelope.reply.send(<A as Handler<M>>::Reply::from_err(err));
+	}
+
+	fn message_name(&self) -> &'static str {
+		type_name::<M>()
+	}
+
+	fn deadline(&self) -> Option<Instant> {
+		self.deadline
+	}
+
+	fn set_deadline(&mut self, deadline: Instant) {
+		self.deadline = Some(deadline);
+	}
+
+	fn sent_at(&self) -> Option<Instant> {
+		self.sent
+	}
+
+	fn set_sent_at(&mut self, sent: Instant) {
+		self.sent = Some(sent);
+	}
+}
@@ -0,0 +1,507 @@
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+
+use crate::channel::ActorChannel;
+use crate::channel::ActorPermit;
+use crate::channel::ActorReceiver;
+use crate::channel::ActorSender;
+use crate::channel::TrySendError;
+use crate::envelope::Envelope;
+use crate::error::ActorError;
+use crate::error::FromError;
+use crate::link::ActorLike;
+use crate::multi::Multi;
+
+/// What an [`OverflowChannel`] does with a message that does not fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Wait until there is room.
+    Block,
+    /// Drop the new message.
+    DropNewest,
+    /// Drop the oldest queued message.
+    DropOldest,
+    /// Fail the send with [`OverflowError::Full`].
+    Reject,
+    /// Replace the queued message with the same [`key`](OverflowPolicy::key)
+    /// in place, even while there is room. A message without a match that
+    /// does not fit makes room as with [`DropOldest`](Self::DropOldest).
+    Coalesce,
+}
+
+/// The overflow policy of an [`OverflowChannel`].
+///
+/// The provided policies cover everything but coalescing, which needs to
+/// know how to key messages:
+///
+/// ```ignore
+/// struct LatestState;
+///
+/// impl OverflowPolicy<Multi<Ui>> for LatestState {
+///     type Key = WidgetId;
+///
+///     fn overflow() -> Overflow {
+///         Overflow::Coalesce
+///     }
+///
+///     fn key(msg: &Multi<Ui>) -> Option<WidgetId> {
+///         msg.downcast_ref::<SetState>().map(|state| state.widget)
+///     }
+/// }
+/// ```
+pub trait OverflowPolicy<T>: Send + Sync + 'static {
+    /// Identifies messages that may replace each other.
+    type Key: Eq + Send + Sync + 'static;
+
+    fn overflow() -> Overflow;
+
+    /// The coalescing key of `msg`, if it has one.
+    fn key(_msg: &T) -> Option<Self::Key> {
+        None
+    }
+}
+
+macro_rules! policy {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        pub struct $name;
+
+        impl<T> OverflowPolicy<T> for $name {
+            type Key = ();
+
+            fn overflow() -> Overflow {
+                Overflow::$name
+            }
+        }
+    };
+}
+
+policy!(
+    /// See [`Overflow::Block`].
+    Block
+);
+policy!(
+    /// See [`Overflow::DropNewest`].
+    DropNewest
+);
+policy!(
+    /// See [`Overflow::DropOldest`].
+    DropOldest
+);
+policy!(
+    /// See [`Overflow::Reject`].
+    Reject
+);
+
+/// A message that can be answered with an error instead of being handled.
+pub trait Rejectable: Send + 'static {
+    fn reject(self, err: ActorError);
+}
+
+impl<A: ActorLike> Rejectable for Multi<A> {
+    fn reject(self, err: ActorError) {
+        self.handler.reject(err)
+    }
+}
+
+impl<T, R> Rejectable for Envelope<T, R>
+where
+    T: Send + 'static,
+    R: FromError<ActorError> + Send + 'static,
+{
+    fn reject(self, err: ActorError) {
+        let _ = self.reply.send(R::from_err(err));
+    }
+}
+
+/// Why a send to an [`OverflowChannel`] failed. Carries the message back.
+#[derive(thiserror::Error)]
+pub enum OverflowError<T> {
+    #[error("Mailbox is full")]
+    Full(T),
+    #[error("Actor is already dead")]
+    Closed(T),
+}
+
+impl<T> std::fmt::Debug for OverflowError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverflowError::Full(_) => f.write_str("Full(..)"),
+            OverflowError::Closed(_) => f.write_str("Closed(..)"),
+        }
+    }
+}
+
+/// A bounded mailbox that does something other than block when it is full.
+///
+/// The overflow policy `P` decides what happens to a message that does not
+/// fit:
+///
+/// - [`Block`]: wait for room, like [`MpscChannel`](crate::MpscChannel).
+/// - [`DropNewest`]: drop the new message.
+/// - [`DropOldest`]: drop the oldest queued message to make room.
+/// - [`Reject`]: fail the send with [`OverflowError::Full`].
+/// - [`Overflow::Coalesce`]: replace the queued message with the same
+///   [`key`](OverflowPolicy::key) on every send, see [`OverflowPolicy`].
+///
+/// Dropped messages are counted (see [`Link::dropped`](crate::Link::dropped))
+/// and, if someone waits for their reply, answered with
+/// [`ActorError::Overflow`].
+///
+/// ```ignore
+/// impl Actor for Telemetry {
+///     type Message = Multi<Self>;
+///     type Channel = OverflowChannel<Self::Message, DropOldest>;
+///     // ...
+/// }
+/// ```
+pub struct OverflowChannel<T, P> {
+    _t: PhantomData<(T, P)>,
+}
+
+impl<T, P> ActorChannel for OverflowChannel<T, P>
+where
+    T: Rejectable + Sync,
+    P: OverflowPolicy<T>,
+{
+    type Message = T;
+    type Receiver = OverflowReceiver<T, P>;
+    type Sender = OverflowSender<T, P>;
+
+    fn create(buffer: usize) -> (Self::Sender, Self::Receiver) {
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue {
+                items: VecDeque::new(),
+                reserved: 0,
+                closed: false,
+            }),
+            capacity: buffer,
+            readable: Notify::new(),
+            writable: Notify::new(),
+            closed: Notify::new(),
+            senders: AtomicUsize::new(1),
+            dropped: AtomicU64::new(0),
+        });
+        let tx = OverflowSender {
+            shared: shared.clone(),
+        };
+        (tx, OverflowReceiver { shared })
+    }
+}
+
+struct Shared<T, P: OverflowPolicy<T>> {
+    queue: Mutex<Queue<T, P::Key>>,
+    capacity: usize,
+    /// A message was queued, or the last sender is gone.
+    readable: Notify,
+    /// A slot was freed, or the channel was closed.
+    writable: Notify,
+    closed: Notify,
+    senders: AtomicUsize,
+    dropped: AtomicU64,
+}
+
+struct Queue<T, K> {
+    items: VecDeque<(Option<K>, T)>,
+    /// Slots held by unused permits.
+    reserved: usize,
+    closed: bool,
+}
+
+impl<T, K: Eq> Queue<T, K> {
+    fn used(&self) -> usize {
+        self.items.len() + self.reserved
+    }
+
+    /// Swaps `value` in for the queued message with the same key, handing
+    /// back the message it replaced, or `value` if none matches.
+    fn replace(&mut self, key: Option<&K>, value: T) -> Result<T, T> {
+        let pending = key.and_then(|key| {
+            self.items
+                .iter_mut()
+                .find(|(pending, _)| pending.as_ref() == Some(key))
+        });
+        match pending {
+            Some((_, pending)) => Ok(std::mem::replace(pending, value)),
+            None => Err(value),
+        }
+    }
+}
+
+impl<T: Rejectable, P: OverflowPolicy<T>> Shared<T, P> {
+    fn drop_message(&self, msg: T) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+        msg.reject(ActorError::Overflow);
+    }
+
+    /// Queues `value` if there is room or the policy makes room. Hands it
+    /// back if the sender has to wait.
+    fn push(&self, value: T) -> Result<Result<(), OverflowError<T>>, T> {
+        let mut queue = self.queue.lock();
+        if queue.closed {
+            return Ok(Err(OverflowError::Closed(value)));
+        }
+
+        let key = P::key(&value);
+        let value = match P::overflow() {
+            // The latest message for a key wins, whether or not the mailbox
+            // is full.
+            Overflow::Coalesce => match queue.replace(key.as_ref(), value) {
+                Ok(stale) => {
+                    drop(queue);
+                    self.drop_message(stale);
+                    return Ok(Ok(()));
+                }
+                Err(value) => value,
+            },
+            _ => value,
+        };
+        let dropped = if queue.used() < self.capacity {
+            queue.items.push_back((key, value));
+            None
+        } else {
+            match P::overflow() {
+                Overflow::Block => return Err(value),
+                Overflow::Reject => return Ok(Err(OverflowError::Full(value))),
+                Overflow::DropNewest => Some(value),
+                Overflow::DropOldest | Overflow::Coalesce => match queue.items.pop_front() {
+                    Some((_, oldest)) => {
+                        queue.items.push_back((key, value));
+                        Some(oldest)
+                    }
+                    // Every slot is reserved.
+                    None => Some(value),
+                },
+            }
+        };
+        drop(queue);
+
+        match dropped {
+            Some(msg) => self.drop_message(msg),
+            None => self.readable.notify_one(),
+        }
+        Ok(Ok(()))
+    }
+}
+
+impl<T, P: OverflowPolicy<T>> Shared<T, P> {
+    /// Stops accepting messages and wakes everyone waiting to send. Returns
+    /// what is still queued.
+    fn close(&self) -> VecDeque<(Option<P::Key>, T)> {
+        let mut queue = self.queue.lock();
+        queue.closed = true;
+        let pending = std::mem::take(&mut queue.items);
+        drop(queue);
+        self.writable.notify_waiters();
+        self.closed.notify_waiters();
+        pending
+    }
+}
+
+pub struct OverflowSender<T, P: OverflowPolicy<T>> {
+    shared: Arc<Shared<T, P>>,
+}
+
+impl<T, P: OverflowPolicy<T>> Clone for OverflowSender<T, P> {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::Relaxed);
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T, P: OverflowPolicy<T>> Drop for OverflowSender<T, P> {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.shared.readable.notify_one();
+        }
+    }
+}
+
+impl<T, P> ActorSender<T> for OverflowSender<T, P>
+where
+    T: Rejectable + Sync,
+    P: OverflowPolicy<T>,
+{
+    type Error = OverflowError<T>;
+    type Permit = OverflowPermit<T, P>;
+
+    async fn send(&self, mut value: T) -> Result<(), Self::Error> {
+        loop {
+            let mut writable = pin!(self.shared.writable.notified());
+            writable.as_mut().enable();
+            match self.shared.push(value) {
+                Ok(result) => return result,
+                Err(back) => value = back,
+            }
+            writable.await;
+        }
+    }
+
+    fn try_send(&self, value: T) -> Result<(), TrySendError<T, Self::Error>> {
+        match self.shared.push(value) {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(OverflowError::Full(value))) | Err(value) => Err(TrySendError::Full(value)),
+            Ok(Err(closed)) => Err(TrySendError::Closed(closed)),
+        }
+    }
+
+    async fn reserve(&self) -> Option<Self::Permit> {
+        loop {
+            let mut writable = pin!(self.shared.writable.notified());
+            writable.as_mut().enable();
+            {
+                let mut queue = self.shared.queue.lock();
+                if queue.closed {
+                    return None;
+                }
+                if queue.used() < self.shared.capacity {
+                    queue.reserved += 1;
+                    return Some(OverflowPermit {
+                        tx: Some(self.clone()),
+                    });
+                }
+            }
+            writable.await;
+        }
+    }
+
+    async fn closed(&self) -> () {
+        loop {
+            let mut closed = pin!(self.shared.closed.notified());
+            closed.as_mut().enable();
+            if self.shared.queue.lock().closed {
+                return;
+            }
+            closed.await;
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.shared.queue.lock().closed
+    }
+
+    fn capacity(&self) -> usize {
+        self.shared
+            .capacity
+            .saturating_sub(self.shared.queue.lock().used())
+    }
+
+    fn max_capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// A slot reserved in an [`OverflowChannel`].
+pub struct OverflowPermit<T, P: OverflowPolicy<T>> {
+    tx: Option<OverflowSender<T, P>>,
+}
+
+impl<T, P> ActorPermit<T> for OverflowPermit<T, P>
+where
+    T: Rejectable + Sync,
+    P: OverflowPolicy<T>,
+{
+    fn send(mut self, value: T) {
+        let Some(tx) = self.tx.take() else { return };
+        let mut queue = tx.shared.queue.lock();
+        queue.reserved -= 1;
+        if queue.closed {
+            drop(queue);
+            // Like a send to a dead actor: the reply, if any, is dropped.
+            drop(value);
+            tx.shared.writable.notify_one();
+            return;
+        }
+        let key = P::key(&value);
+        let value = match P::overflow() {
+            Overflow::Coalesce => match queue.replace(key.as_ref(), value) {
+                Ok(stale) => {
+                    drop(queue);
+                    // The reserved slot was not needed after all.
+                    tx.shared.writable.notify_one();
+                    tx.shared.drop_message(stale);
+                    return;
+                }
+                Err(value) => value,
+            },
+            _ => value,
+        };
+        queue.items.push_back((key, value));
+        drop(queue);
+        tx.shared.readable.notify_one();
+    }
+}
+
+impl<T, P: OverflowPolicy<T>> Drop for OverflowPermit<T, P> {
+    fn drop(&mut self) {
+        if let Some(tx) = self.tx.take() {
+            tx.shared.queue.lock().reserved -= 1;
+            tx.shared.writable.notify_one();
+        }
+    }
+}
+
+pub struct OverflowReceiver<T, P: OverflowPolicy<T>> {
+    shared: Arc<Shared<T, P>>,
+}
+
+impl<T, P> ActorReceiver<T> for OverflowReceiver<T, P>
+where
+    T: Rejectable + Sync,
+    P: OverflowPolicy<T>,
+{
+    async fn recv(&mut self) -> Option<T> {
+        loop {
+            let mut readable = pin!(self.shared.readable.notified());
+            readable.as_mut().enable();
+            {
+                let mut queue = self.shared.queue.lock();
+                if let Some((_, value)) = queue.items.pop_front() {
+                    drop(queue);
+                    self.shared.writable.notify_one();
+                    return Some(value);
+                }
+                if queue.closed || self.shared.senders.load(Ordering::Acquire) == 0 {
+                    return None;
+                }
+            }
+            readable.await;
+        }
+    }
+
+    fn is_closed(&mut self) -> bool {
+        self.shared.queue.lock().closed
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.shared.queue.lock().items.is_empty()
+    }
+
+    fn close(&mut self) {
+        let pending = self.shared.close();
+        // Queued messages can still be received.
+        self.shared.queue.lock().items = pending;
+    }
+}
+
+impl<T, P: OverflowPolicy<T>> Drop for OverflowReceiver<T, P> {
+    fn drop(&mut self) {
+        // Nobody will handle what is still queued; the senders see the
+        // reply dropped.
+        drop(self.shared.close());
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use tokio::runtime::Handle;
+use tokio::task::AbortHandle;
+use tokio::task::JoinSet;
+use tracing::Instrument;
+
+use crate::actor::Actor;
+use crate::actor::ActorContext;
+use crate::actor::ActorMessage as _;
+use crate::actor::Isolate;
+use crate::autoscale::QueueTime;
+use crate::cancel::CancelReason;
+use crate::cancel::CancelToken;
+use crate::channel::ActorChannel;
+use crate::channel::ActorReceiver as _;
+use crate::children::Children;
+use crate::monitor::ActorExit;
+use crate::spawn::Lifecycle;
+use crate::weak::WeakLink;
+
+type Resume = Box<dyn FnOnce() -> AbortHandle + Send>;
+
+/// Where a passivated actor waits, without a task, for its next message.
+///
+/// Shared by the actor's link, which [`wake`](Self::wake)s it after every
+/// send, and its token, which wakes it to stop.
+pub(crate) struct Parking(Mutex<Slot>);
+
+enum Slot {
+    /// The actor has a task; the handle of the task it was last woken in.
+    Running(Option<AbortHandle>),
+    /// The actor is parked; spawns its next task.
+    Parked(Resume),
+}
+
+impl Parking {
+    pub(crate) fn new<C>(token: &CancelToken<C>) -> Arc<Self>
+    where
+        C: Clone + Send + Sync + 'static,
+    {
+        let parking = Arc::new(Self(Mutex::new(Slot::Running(None))));
+        let weak = Arc::downgrade(&parking);
+        token.on_cancel(move |_| {
+            if let Some(parking) = weak.upgrade() {
+                parking.wake();
+            }
+        });
+        parking
+    }
+
+    /// Spawns a new task for the actor if it is parked.
+    pub(crate) fn wake(&self) {
+        let mut slot = self.0.lock();
+        match std::mem::replace(&mut *slot, Slot::Running(None)) {
+            Slot::Parked(resume) => *slot = Slot::Running(Some(resume())),
+            running => *slot = running,
+        }
+    }
+
+    /// Aborts the task the actor was last woken in, if any.
+    pub(crate) fn abort(&self) {
+        if let Slot::Running(Some(task)) = &*self.0.lock() {
+            task.abort();
+        }
+    }
+}
+
+/// How a run of the actor's [`Lifecycle`] ended.
+pub(crate) enum Stopped<A: Actor> {
+    Exited(ActorExit<A::Cancel>),
+    /// The actor was passivated; its mailbox is still open.
+    Idle(Box<Parked<A>>),
+}
+
+/// A passivated actor: what is left of it once `terminate` has run, enough
+/// to start a new instance behind the same link.
+pub(crate) struct Parked<A: Actor> {
+    lifecycle: Lifecycle<A>,
+    rx: <A::Channel as ActorChannel>::Receiver,
+    token: CancelToken<A::Cancel>,
+    span: tracing::Span,
+    link: WeakLink<A>,
+    isolate: Option<Isolate<A>>,
+    queue_time: Option<Arc<QueueTime>>,
+    idle: Duration,
+    stash: VecDeque<A::Message>,
+    unstashed: VecDeque<A::Message>,
+}
+
+impl<A: Actor> Parked<A> {
+    pub(crate) fn new(lifecycle: Lifecycle<A>, ctx: ActorContext<A>, idle: Duration) -> Box<Self> {
+        Box::new(Self {
+            lifecycle,
+            rx: ctx.rx,
+            token: ctx.token,
+            span: ctx.span,
+            link: ctx.link,
+            isolate: ctx.isolate,
+            queue_time: ctx.queue_time,
+            idle,
+            stash: ctx.stash,
+            unstashed: ctx.unstashed,
+        })
+    }
+
+    /// Hands the actor over to its [`Parking`], to be woken in a new task.
+    /// Gives it back if it has to go on right away: a message is waiting,
+    /// it was cancelled, or it has nowhere to park.
+    fn park(mut self: Box<Self>) -> Result<(), Box<Self>> {
+        let Some(parking) = self.lifecycle.parking.clone() else {
+            return Err(self);
+        };
+
+        // Senders wake the actor after queueing, so a message queued before
+        // the lock is seen here and one queued after finds it parked.
+        let mut slot = parking.0.lock();
+        if self.token.is_cancelled() || !self.rx.is_empty() || !self.unstashed.is_empty() {
+            drop(slot);
+            return Err(self);
+        }
+
+        let runtime = Handle::current();
+        *slot = Slot::Parked(Box::new(move || runtime.spawn(self.woken()).abort_handle()));
+        Ok(())
+    }
+
+    /// The task of a parked actor once it is woken.
+    fn woken(self: Box<Self>) -> impl Future<Output = ()> + Send + 'static {
+        let span = self.span.clone();
+        let exit = self.lifecycle.exit.clone();
+        async move {
+            let counts = self.lifecycle.counts();
+            if let Some(reason) = settle(self.resume().await).await {
+                drop(counts);
+                exit.send_replace(Some(reason));
+            }
+        }
+        .instrument(span)
+    }
+
+    /// Waits for the next unexpired message and starts a new instance to
+    /// handle it. Gives up after another idle period without one.
+    pub(crate) async fn resume(mut self: Box<Self>) -> Stopped<A> {
+        let msg = loop {
+            tokio::select! {
+                reason = self.token.cancelled_or_dropped() => {
+                    // `terminate` already ran, so a cancellation just ends the actor.
+                    return Stopped::Exited(ActorExit::Completed(reason.unwrap_or_default()));
+                }
+                msg = self.rx.recv() => match msg {
+                    // Expired messages are skipped without waking the actor.
+                    Some(msg) => if let Some(msg) = msg.unless_expired() {
+                        break msg;
+                    },
+                    None => return Stopped::Exited(ActorExit::Completed(CancelReason::default())),
+                },
+                _ = tokio::time::sleep(self.idle) => return Stopped::Idle(self),
+            }
+        };
+
+        let parked = *self;
+        let mut lifecycle = parked.lifecycle;
+        let mut ctx = ActorContext {
+            rx: parked.rx,
+            token: parked.token,
+            futures: JoinSet::new(),
+            span: parked.span,
+            link: parked.link,
+            children: Children::new(lifecycle.system.clone()),
+            restart: None,
+            isolate: parked.isolate,
+            queue_time: parked.queue_time,
+            passivate: Some(parked.idle),
+            passivating: false,
+            receive_timeout: None,
+            stash: parked.stash,
+            unstashed: parked.unstashed,
+            behavior: None,
+        };
+
+        match lifecycle.reinit(&mut ctx).await {
+            Some(init) => lifecycle.run(ctx, init, Some(msg)).await,
+            None => Stopped::Exited(ActorExit::Completed(CancelReason::default())),
+        }
+    }
+}
+
+/// Drives the actor until it exits, which is reported, or parks, which ends
+/// the current task.
+pub(crate) async fn settle<A: Actor>(mut stopped: Stopped<A>) -> Option<ActorExit<A::Cancel>> {
+    loop {
+        match stopped {
+            Stopped::Exited(exit) => return Some(exit),
+            Stopped::Idle(parked) => match parked.park() {
+                Ok(()) => return None,
+                Err(parked) => stopped = parked.resume().await,
+            },
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+
+use crate::actor::SyncTrait;
+use crate::ask::within;
+use crate::channel::ActorChannel;
+use crate::channel::ActorPermit;
+use crate::channel::ActorSender;
+use crate::envelope::Envelope;
+use crate::handler::Handler;
+use crate::link::ActorLike;
+use crate::link::LinkState;
+use crate::link::reply;
+use crate::multi::Multi;
+
+/// A mailbox slot reserved with [`Link::reserve`](crate::Link::reserve).
+///
+/// Sending through the permit never waits. Dropping it unused gives the slot
+/// back.
+#[must_use = "dropping a permit gives its slot back"]
+pub struct Permit<A: ActorLike> {
+    permit: <<A::Channel as ActorChannel>::Sender as ActorSender<A::Message>>::Permit,
+    /// Wraps what is sent and wakes the actor if it is passivated.
+    link: Arc<LinkState<A>>,
+}
+
+impl<A: ActorLike> Permit<A> {
+    pub(crate) fn new(
+        permit: <<A::Channel as ActorChannel>::Sender as ActorSender<A::Message>>::Permit,
+        link: Arc<LinkState<A>>,
+    ) -> Self {
+        Self { permit, link }
+    }
+
+    fn deliver(self, message: A::Message) {
+        self.permit.send(message);
+        self.link.wake();
+    }
+
+    /// Like [`Link::tell_dyn`](crate::Link::tell_dyn), without waiting.
+    pub fn tell_dyn<T>(self, message: T)
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let (envelope, _) = Envelope::<T, <A as Handler<T>>::Reply>::new(message);
+        let multi = self.link.wrap(envelope);
+        self.deliver(multi);
+    }
+
+    /// Like [`Link::ask_dyn_async`](crate::Link::ask_dyn_async), without
+    /// waiting for the send. Returns the future of the reply.
+    pub fn ask_dyn<T>(self, message: T) -> BoxFuture<'static, <A as Handler<T>>::Reply>
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let (envelope, rx) = Envelope::<T, <A as Handler<T>>::Reply>::new(message);
+        let multi = self.link.wrap(envelope);
+        self.deliver(multi);
+
+        within(A::ask_timeout(), reply(rx)).boxed()
+    }
+
+    /// Like [`Link::send_raw`](crate::Link::send_raw), without waiting.
+    pub fn send_raw(self, message: A::Message) {
+        self.deliver(message);
+    }
+}
@@ -1,12 +1,22 @@
+use std::any::type_name;
 use std::sync::Arc;
 
 use arc_swap::ArcSwap;
 use crate::cancel::CancelToken;
+use futures::FutureExt as _;
+use futures::future::select_all;
+use parking_lot::Mutex;
 use tokio::sync::Notify;
 use tokio::sync::mpsc;
+use tokio::sync::watch;
 
 use crate::Actor;
+use crate::ActorError;
+use crate::CrashPolicy;
 use crate::Link;
+use crate::Spawner;
+use crate::restart::RestartPolicy;
+use crate::restart::Restarts;
 use crate::channel::ActorChannel;
 use crate::channel::ActorReceiver as _;
 
@@ -129,4 +139,231 @@ where
 		self.state.store(new_state);
 		self.state_tx.try_send(self.state.load_full()).unwrap();
 	}
+
+	/// Like [`reset`](Self::reset), but paced by `restarts`.
+	///
+	/// Waits out the policy's backoff before spawning the new instance, and
+	/// fails with [`ActorError::RestartLimitExceeded`] without restarting once
+	/// the policy's intensity limit is exceeded.
+	pub async fn restart(&self, restarts: &mut Restarts) -> Result<(), ActorError> {
+		let Some(delay) = restarts.record() else {
+			return Err(ActorError::RestartLimitExceeded);
+		};
+
+		tokio::time::sleep(delay).await;
+		self.reset();
+		Ok(())
+	}
+}
+
+/// How a [`Supervisor`] reacts when one of its children stops.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strategy {
+	/// Restart only the child that stopped.
+	#[default]
+	OneForOne,
+	/// Stop all other children and restart every child.
+	OneForAll,
+	/// Stop the children started after the one that stopped, then restart
+	/// it and them.
+	RestForOne,
+}
+
+/// Why a [`Supervisor`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupervisorExit {
+	/// The supervisor was cancelled or dropped.
+	Shutdown,
+	/// Children failed more often than the [`RestartPolicy`] allows, so the
+	/// supervisor gave up and stopped all of them.
+	Escalated,
+}
+
+/// Owns a fixed set of child actors and restarts them when they stop.
+///
+/// Every child is spawned under the supervisor's [`CancelToken`], so
+/// cancelling the supervisor (or dropping it) tears all of them down. A
+/// child that stops for any other reason is restarted from its spec according
+/// to the supervisor's [`Strategy`], paced by its [`RestartPolicy`]. As in
+/// Erlang/OTP, restart intensity is counted for the supervisor as a whole.
+pub struct Supervisor<A: Actor> {
+	token: CancelToken<A::Cancel>,
+	children: Arc<Mutex<Vec<Link<A>>>>,
+	done: watch::Receiver<Option<SupervisorExit>>,
+}
+
+impl<A: Actor> Supervisor<A>
+where
+	A::Spec: Clone,
+{
+	pub fn new(strategy: Strategy, specs: Vec<A::Spec>) -> Self {
+		Self::with_policy(strategy, RestartPolicy::default(), specs)
+	}
+
+	pub fn with_policy(strategy: Strategy, policy: RestartPolicy, specs: Vec<A::Spec>) -> Self {
+		let token = CancelToken::new();
+		let links: Vec<Link<A>> = specs
+			.iter()
+			.map(|spec| spawn_child(spec.clone(), &token))
+			.collect();
+
+		let children = Arc::new(Mutex::new(links.clone()));
+		let (done_tx, done) = watch::channel(None);
+
+		tokio::spawn({
+			let token = token.clone();
+			let children = children.clone();
+			let restarts = policy.restarts();
+			async move {
+				let exit = supervise(strategy, restarts, specs, links, &token, &children).await;
+				done_tx.send_replace(Some(exit));
+			}
+		});
+
+		Self {
+			token,
+			children,
+			done,
+		}
+	}
+}
+
+impl<A: Actor> Supervisor<A> {
+	/// Links to the current incarnation of every child, in spec order.
+	pub fn children(&self) -> Vec<Link<A>> {
+		self.children.lock().clone()
+	}
+
+	/// Link to the current incarnation of the child at `index`.
+	pub fn child(&self, index: usize) -> Option<Link<A>> {
+		self.children.lock().get(index).cloned()
+	}
+
+	/// The token all children are spawned under.
+	pub fn token(&self) -> &CancelToken<A::Cancel> {
+		&self.token
+	}
+
+	/// Stops the supervisor and all of its children without waiting.
+	pub fn cancel(&self, reason: A::Cancel) {
+		self.token.cancel(reason)
+	}
+
+	/// Resolves once the supervisor has stopped and all children are gone.
+	pub async fn wait(&self) -> SupervisorExit {
+		let mut done = self.done.clone();
+		match done.wait_for(Option::is_some).await {
+			Ok(exit) => exit.unwrap_or(SupervisorExit::Shutdown),
+			Err(_) => SupervisorExit::Shutdown,
+		}
+	}
+
+	/// Stops the supervisor and waits until all children are gone.
+	pub async fn shutdown(&self) -> SupervisorExit {
+		self.token.cancel(A::Cancel::default());
+		self.wait().await
+	}
+}
+
+impl<A: Actor> Drop for Supervisor<A> {
+	fn drop(&mut self) {
+		self.token.cancel(A::Cancel::default())
+	}
+}
+
+/// Children stop on a crash and leave restarting to the supervisor.
+fn spawn_child<A: Actor>(spec: A::Spec, token: &CancelToken<A::Cancel>) -> Link<A> {
+	Spawner::new(spec)
+		.parent(token)
+		.crash_policy(CrashPolicy::Stop)
+		.spawn()
+}
+
+async fn supervise<A: Actor>(
+	strategy: Strategy,
+	mut restarts: Restarts,
+	specs: Vec<A::Spec>,
+	mut links: Vec<Link<A>>,
+	token: &CancelToken<A::Cancel>,
+	children: &Mutex<Vec<Link<A>>>,
+) -> SupervisorExit
+where
+	A::Spec: Clone,
+{
+	let mut exit = SupervisorExit::Shutdown;
+
+	loop {
+		if links.is_empty() {
+			token.cancelled().await;
+			break;
+		}
+
+		let stopped = {
+			let waits = links
+				.iter()
+				.enumerate()
+				.map(|(index, link)| link.join().map(move |_| index).boxed());
+
+			tokio::select! {
+				_ = token.cancelled() => None,
+				(index, _, _) = select_all(waits) => Some(index),
+			}
+		};
+
+		let Some(index) = stopped else {
+			break;
+		};
+
+		// The child may have stopped because the whole tree is going down.
+		if token.is_cancelled() {
+			break;
+		}
+
+		let Some(delay) = restarts.record() else {
+			tracing::error!(
+				"Supervised actor {} #{index} stopped too often, escalating",
+				type_name::<A>()
+			);
+			exit = SupervisorExit::Escalated;
+			token.cancel(A::Cancel::default());
+			break;
+		};
+
+		let restart = match strategy {
+			Strategy::OneForOne => index..index + 1,
+			Strategy::OneForAll => 0..links.len(),
+			Strategy::RestForOne => index..links.len(),
+		};
+
+		tracing::warn!(
+			"Supervised actor {} #{index} stopped, restarting {:?} ({strategy:?})",
+			type_name::<A>(),
+			restart
+		);
+
+		// Stop siblings in reverse start order before bringing anyone back, and
+		// wait for each to finish its `terminate` so no two instances overlap.
+		for link in links[restart.clone()].iter().rev() {
+			link.cancel(A::Cancel::default());
+			link.join().await;
+		}
+
+		tokio::select! {
+			_ = token.cancelled() => break,
+			_ = tokio::time::sleep(delay) => {}
+		}
+
+		for index in restart {
+			links[index] = spawn_child(specs[index].clone(), token);
+		}
+
+		*children.lock() = links.clone();
+	}
+
+	// Children were cancelled through the token tree; wait for them to finish.
+	for link in &links {
+		link.join().await;
+	}
+
+	exit
 }
@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// Limits how often, and how quickly, a failing actor is brought back.
+///
+/// At most `max_restarts` restarts are allowed within any `within` window;
+/// exceeding that means the failure is not transient and should be escalated
+/// instead of retried. Restarts inside the window back off exponentially,
+/// starting at `min_backoff` and capped at `max_backoff`, with up to `jitter`
+/// (a fraction of the delay) added at random so that siblings don't restart
+/// in lockstep.
+///
+/// The bookkeeping lives in [`Restarts`]. A [`Supervisor`](crate::Supervisor)
+/// keeps one for all its children, so restart intensity is counted for the
+/// supervisor as a whole; one can also be used with
+/// [`Proxy::restart`](crate::Proxy::restart) for hand-rolled restarts.
+/// Delays are measured with [`tokio::time`], so they follow tokio's paused
+/// clock in tests.
+#[derive(Debug, Clone)]
+pub struct RestartPolicy {
+    /// Maximum number of restarts allowed within `within`.
+    pub max_restarts: usize,
+    /// Length of the sliding window restarts are counted in.
+    pub within: Duration,
+    /// Delay before the first restart in a burst.
+    pub min_backoff: Duration,
+    /// Upper bound for the exponential backoff.
+    pub max_backoff: Duration,
+    /// Random extra delay, as a fraction of the computed delay (`0.0..=1.0`).
+    pub jitter: f64,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: 3,
+            within: Duration::from_secs(5),
+            min_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Starts tracking restarts against this policy.
+    pub fn restarts(&self) -> Restarts {
+        Restarts {
+            policy: self.clone(),
+            history: VecDeque::new(),
+        }
+    }
+
+    fn backoff(&self, attempt: usize) -> Duration {
+        let factor = 1u32.checked_shl(attempt as u32).unwrap_or(u32::MAX);
+        let delay = self
+            .min_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return delay;
+        }
+
+        delay + delay.mul_f64(jitter * random_unit())
+    }
+}
+
+/// Restart history of one actor, checked against a [`RestartPolicy`].
+#[derive(Debug, Clone)]
+pub struct Restarts {
+    policy: RestartPolicy,
+    history: VecDeque<Instant>,
+}
+
+impl Restarts {
+    /// Records a restart and returns how long to wait before performing it.
+    ///
+    /// Returns `None` once the policy's intensity limit is exceeded; the
+    /// restart is not recorded in that case and the failure should be
+    /// escalated.
+    pub fn record(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        while let Some(first) = self.history.front() {
+            if now.duration_since(*first) < self.policy.within {
+                break;
+            }
+            self.history.pop_front();
+        }
+
+        if self.history.len() >= self.policy.max_restarts {
+            return None;
+        }
+
+        let delay = self.policy.backoff(self.history.len());
+        self.history.push_back(now);
+        Some(delay)
+    }
+
+    /// Forgets all recorded restarts.
+    pub fn reset(&mut self) {
+        self.history.clear();
+    }
+
+    /// The policy these restarts are checked against.
+    pub fn policy(&self) -> &RestartPolicy {
+        &self.policy
+    }
+}
+
+/// A random number in `0.0..1.0`, good enough for jitter.
+fn random_unit() -> f64 {
+    let bits = RandomState::new().hash_one(Instant::now());
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
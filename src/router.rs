@@ -0,0 +1,297 @@
+use std::any::Any;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::hash::RandomState;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use arc_swap::ArcSwap;
+
+use crate::actor::SyncTrait;
+use crate::error::ActorError;
+use crate::error::FromError;
+use crate::handler::Handler;
+use crate::link::ActorLike;
+use crate::link::Link;
+use crate::multi::Multi;
+
+/// Points each member takes on the hash ring; more points spread the keys
+/// more evenly.
+const RING_POINTS: usize = 64;
+
+/// How a [`Router`] picks the member that gets a message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Routing {
+    /// Each member in turn.
+    #[default]
+    RoundRobin,
+    /// A member chosen at random.
+    Random,
+    /// The member with the fewest queued messages; ties go round-robin.
+    LeastLoaded,
+    /// The member owning the message's key on a hash ring, see
+    /// [`Router::hash_key`]. Adding or removing a member only moves the keys
+    /// of that member. Messages without a key go round-robin.
+    ConsistentHash,
+}
+
+type KeyFn = Arc<dyn Fn(&dyn Any) -> u64 + Send + Sync>;
+
+/// Dispatches messages across a changing set of actors of the same type.
+///
+/// A router offers the messaging surface of a [`Link`] (`ask_dyn`,
+/// `tell_dyn`, ...) and forwards each message to one member picked by its
+/// [`Routing`], or to all of them with [`broadcast_dyn`](Self::broadcast_dyn)
+/// and [`gather_dyn`](Self::gather_dyn). Members can be added and removed at
+/// any time; members whose actor has stopped (see [`Link::alive`]) are
+/// dropped the next time a message is routed.
+///
+/// ```ignore
+/// let router = Router::new(Routing::ConsistentHash)
+///     .hash_key(|get: &Get| get.user_id)
+///     .with_members((0..4).map(|_| spawn::<Cache>(())));
+/// let user = router.ask_dyn(Get { user_id: 7 }).await?;
+/// ```
+pub struct Router<A: ActorLike> {
+    routing: Routing,
+    members: ArcSwap<Members<A>>,
+    keys: HashMap<TypeId, KeyFn>,
+    next: AtomicUsize,
+    random: AtomicU64,
+}
+
+/// A snapshot of the members, replaced as a whole on every change.
+struct Members<A: ActorLike> {
+    links: Vec<Link<A>>,
+    /// Hash ring points and the index of the member owning each, sorted.
+    /// Only built for [`Routing::ConsistentHash`].
+    ring: Vec<(u64, usize)>,
+}
+
+impl<A: ActorLike> Members<A> {
+    fn new(links: Vec<Link<A>>, routing: Routing) -> Self {
+        let mut ring = Vec::new();
+        if routing == Routing::ConsistentHash {
+            for (index, link) in links.iter().enumerate() {
+                ring.extend((0..RING_POINTS).map(|point| (hash(&(link, point)), index)));
+            }
+            ring.sort_unstable();
+        }
+        Self { links, ring }
+    }
+
+    /// The member owning `key`: the first ring point at or after it.
+    fn owner(&self, key: u64) -> Option<&Link<A>> {
+        let at = self.ring.partition_point(|(point, _)| *point < key);
+        let (_, index) = self.ring.get(at).or(self.ring.first())?;
+        self.links.get(*index)
+    }
+}
+
+fn hash(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<A: ActorLike> Router<A> {
+    /// An empty router.
+    pub fn new(routing: Routing) -> Self {
+        Self {
+            routing,
+            members: ArcSwap::from_pointee(Members::new(Vec::new(), routing)),
+            keys: HashMap::new(),
+            next: AtomicUsize::new(0),
+            // Any non-zero seed will do for xorshift.
+            random: AtomicU64::new(RandomState::new().hash_one(0u64) | 1),
+        }
+    }
+
+    /// Add `links` as members.
+    pub fn with_members(self, links: impl IntoIterator<Item = Link<A>>) -> Self {
+        for link in links {
+            self.add(link);
+        }
+        self
+    }
+
+    /// Route `M` messages by the key `key` extracts, with
+    /// [`Routing::ConsistentHash`]. Messages with equal keys go to the same
+    /// member as long as the members do not change.
+    pub fn hash_key<M, K>(mut self, key: impl Fn(&M) -> K + Send + Sync + 'static) -> Self
+    where
+        M: SyncTrait,
+        A: Handler<M>,
+        K: Hash,
+    {
+        let key: KeyFn = Arc::new(move |message: &dyn Any| {
+            let message = message.downcast_ref::<M>().expect("key function for another message type");
+            hash(&key(message))
+        });
+        self.keys.insert(TypeId::of::<M>(), key);
+        self
+    }
+
+    pub fn routing(&self) -> Routing {
+        self.routing
+    }
+
+    /// Add `link` as a member, unless it already is one.
+    pub fn add(&self, link: Link<A>) {
+        self.members.rcu(|members| {
+            let mut links = members.links.clone();
+            if !links.contains(&link) {
+                links.push(link.clone());
+            }
+            Members::new(links, self.routing)
+        });
+    }
+
+    /// Remove `link` from the members. Returns `false` if it was not one.
+    pub fn remove(&self, link: &Link<A>) -> bool {
+        let mut removed = false;
+        self.members.rcu(|members| {
+            let links: Vec<_> = members.links.iter().filter(|member| *member != link).cloned().collect();
+            removed = links.len() < members.links.len();
+            Members::new(links, self.routing)
+        });
+        removed
+    }
+
+    /// The current members, including any that stopped since the last
+    /// message was routed.
+    pub fn members(&self) -> Vec<Link<A>> {
+        self.members.load().links.clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.load().links.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The members, after dropping those whose actor has stopped.
+    fn live(&self) -> Arc<Members<A>> {
+        let members = self.members.load_full();
+        if members.links.iter().all(Link::alive) {
+            return members;
+        }
+        self.members.rcu(|members| {
+            let links = members.links.iter().filter(|link| link.alive()).cloned().collect();
+            Members::new(links, self.routing)
+        });
+        self.members.load_full()
+    }
+
+    /// Drops the members whose actor has stopped and returns the others.
+    pub(crate) fn prune(&self) -> Vec<Link<A>> {
+        self.live().links.clone()
+    }
+
+    /// The member that gets `message`, or `None` if there are no members.
+    fn route<T: SyncTrait>(&self, message: &T) -> Option<Link<A>> {
+        let members = self.live();
+        let links = &members.links;
+        if links.is_empty() {
+            return None;
+        }
+
+        let link = match self.routing {
+            Routing::RoundRobin => self.round_robin(links),
+            Routing::Random => &links[self.random() as usize % links.len()],
+            Routing::LeastLoaded => {
+                // Start the scan at the round-robin position so ties rotate.
+                let start = self.next.fetch_add(1, Ordering::Relaxed);
+                (0..links.len())
+                    .map(|offset| &links[(start + offset) % links.len()])
+                    .min_by_key(|link| link.len())?
+            }
+            Routing::ConsistentHash => match self.keys.get(&TypeId::of::<T>()) {
+                Some(key) => members.owner(key(message))?,
+                None => self.round_robin(links),
+            },
+        };
+        Some(link.clone())
+    }
+
+    fn round_robin<'a>(&self, links: &'a [Link<A>]) -> &'a Link<A> {
+        &links[self.next.fetch_add(1, Ordering::Relaxed) % links.len()]
+    }
+
+    /// xorshift64; routing only needs an even spread, not good randomness.
+    fn random(&self) -> u64 {
+        let mut next = 0;
+        let _ = self.random.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |mut x| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            next = x;
+            Some(x)
+        });
+        next
+    }
+
+    /// Like [`Link::ask_dyn`], to the member picked by the routing. Resolves
+    /// to [`ActorError::NoMembers`] if there is none.
+    pub async fn ask_dyn<T>(&self, message: T) -> <A as Handler<T>>::Reply
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        match self.route(&message) {
+            Some(link) => link.ask_dyn(message).await,
+            None => <A as Handler<T>>::Reply::from_err(ActorError::NoMembers),
+        }
+    }
+
+    /// Like [`Link::tell_dyn`], to the member picked by the routing. The
+    /// message is dropped if there is none.
+    pub async fn tell_dyn<T>(&self, message: T)
+    where
+        T: SyncTrait,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        if let Some(link) = self.route(&message) {
+            link.tell_dyn(message).await;
+        }
+    }
+
+    /// Like [`Link::tell_dyn`], to every member.
+    pub async fn broadcast_dyn<T>(&self, message: T)
+    where
+        T: SyncTrait + Clone,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let members = self.live();
+        futures::future::join_all(members.links.iter().map(|link| link.tell_dyn(message.clone()))).await;
+    }
+
+    /// Like [`Link::ask_dyn`], to every member. The replies come in the
+    /// order of [`members`](Self::members).
+    pub async fn gather_dyn<T>(&self, message: T) -> Vec<<A as Handler<T>>::Reply>
+    where
+        T: SyncTrait + Clone,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let members = self.live();
+        futures::future::join_all(members.links.iter().map(|link| link.ask_dyn(message.clone()))).await
+    }
+}
+
+impl<A: ActorLike> Default for Router<A> {
+    fn default() -> Self {
+        Self::new(Routing::default())
+    }
+}
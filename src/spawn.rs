@@ -0,0 +1,776 @@
+use std::any::Any;
+use std::any::type_name;
+use std::future::Future;
+use std::ops::ControlFlow;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use futures::future::Either;
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tracing::Instrument;
+
+use crate::actor::Actor;
+use crate::actor::ActorContext;
+use crate::actor::ActorMessage as _;
+use crate::actor::Init;
+use crate::actor::Isolate;
+use crate::actor::Terminate;
+use crate::autoscale::AutoscalePolicy;
+use crate::autoscale::AutoscalePool;
+use crate::autoscale::QueueTime;
+use crate::cancel::CancelReason;
+use crate::cancel::CancelToken;
+use crate::channel::ActorChannel;
+use crate::channel::ActorReceiver as _;
+use crate::children::Children;
+use crate::count::Count;
+use crate::crash::Crash;
+use crate::crash::CrashPolicy;
+use crate::crash::Escalation;
+use crate::handler::Exec;
+use crate::link::Link;
+use crate::monitor::ActorExit;
+use crate::monitor::ExitSender;
+use crate::passivation::Parked;
+use crate::passivation::Parking;
+use crate::passivation::Stopped;
+use crate::passivation::settle;
+use crate::restart::RestartPolicy;
+use crate::restart::Restarts;
+use crate::system::ActorSystem;
+use crate::system::WeakSystem;
+
+type Respawn<A> = Box<dyn FnMut() -> <A as Actor>::Spec + Send>;
+type InitResult<A> = BoxFuture<'static, Result<A, <A as Actor>::Cancel>>;
+
+/// Builder for spawning an actor with non-default wiring.
+///
+/// [`Actor::spawn`] is a shorthand for `Spawner::new(spec).spawn()`. Use the
+/// builder directly when the actor has to be attached to an existing
+/// [`CancelToken`] tree, e.g. by a [`Supervisor`](crate::Supervisor), or
+/// when it needs a [`CrashPolicy`] other than the default.
+pub struct Spawner<A: Actor> {
+    spec: A::Spec,
+    token: Option<CancelToken<A::Cancel>>,
+    respawn: Option<Respawn<A>>,
+    crash: CrashPolicy,
+    escalate: Option<Escalation>,
+    restarts: RestartPolicy,
+    system: Option<ActorSystem>,
+    init_timeout: Option<Duration>,
+    init_retry: Option<RestartPolicy>,
+    passivate: Option<Duration>,
+    catch_panics: bool,
+    queue_time: Option<Arc<QueueTime>>,
+}
+
+impl<A: Actor> Spawner<A> {
+    pub fn new(spec: A::Spec) -> Self {
+        Self {
+            spec,
+            token: None,
+            respawn: None,
+            crash: CrashPolicy::default(),
+            escalate: None,
+            restarts: RestartPolicy::default(),
+            system: None,
+            init_timeout: None,
+            init_retry: None,
+            passivate: None,
+            catch_panics: false,
+            queue_time: None,
+        }
+    }
+
+    /// Spawn the actor under `parent`.
+    ///
+    /// The actor's token becomes a [`child`](CancelToken::child) of `parent`,
+    /// so cancelling the parent cancels the actor as well.
+    pub fn parent(mut self, parent: &CancelToken<A::Cancel>) -> Self {
+        self.token = Some(parent.child());
+        self
+    }
+
+    /// Spawn the actor in `system`, under its root token.
+    pub fn system(mut self, system: &ActorSystem) -> Self {
+        self = self.under(system.token());
+        self.system = Some(system.clone());
+        self
+    }
+
+    /// Spawn the actor under a token of another actor type, which cancels it
+    /// with the default reason.
+    pub(crate) fn under<P>(mut self, parent: &CancelToken<P>) -> Self
+    where
+        P: Clone + Send + Sync + 'static,
+    {
+        self.token = Some(parent.child_map(|_| A::Cancel::default()));
+        self
+    }
+
+    /// Spawn the actor as a child of another actor: under `parent`'s token,
+    /// in the parent's `system` and escalating to `escalate`, unless the
+    /// builder already names a system or an escalation handler.
+    pub(crate) fn child_of<P>(
+        mut self,
+        parent: &CancelToken<P>,
+        system: Option<ActorSystem>,
+        escalate: Escalation,
+    ) -> Self
+    where
+        P: Clone + Send + Sync + 'static,
+    {
+        if self.system.is_none() {
+            self.system = system;
+        }
+        self.escalate.get_or_insert(escalate);
+        self.under(parent)
+    }
+
+    /// What to do when the actor's task panics.
+    pub fn crash_policy(mut self, policy: CrashPolicy) -> Self {
+        self.crash = policy;
+        self
+    }
+
+    /// Where [`CrashPolicy::Escalate`] sends the actor's crashes.
+    pub fn on_escalate(mut self, escalate: impl Fn(Crash) + Send + Sync + 'static) -> Self {
+        self.escalate = Some(Arc::new(escalate));
+        self
+    }
+
+    /// Paces in-place restarts done by [`CrashPolicy::Restart`].
+    pub fn restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restarts = policy;
+        self
+    }
+
+    /// Catch a panic in each message handler, answer the caller with
+    /// [`ActorError::HandlerPanicked`](crate::ActorError::HandlerPanicked)
+    /// and let [`Actor::on_handler_panic`] decide what happens to the actor.
+    ///
+    /// Without it a handler panic crashes the actor, which then goes through
+    /// its [`CrashPolicy`]. Only [`Multi`](crate::Multi) messages are caught
+    /// this way.
+    pub fn catch_handler_panics(mut self) -> Self {
+        self.catch_panics = true;
+        self
+    }
+
+    /// Report how long each [`Multi`](crate::Multi) message waited in the
+    /// mailbox to `queue_time`.
+    pub(crate) fn report_queue_time(mut self, queue_time: Arc<QueueTime>) -> Self {
+        self.queue_time = Some(queue_time);
+        self
+    }
+
+    /// Fail `init` that takes longer than `timeout`, reported as
+    /// [`ActorExit::InitTimedOut`].
+    pub fn init_timeout(mut self, timeout: Duration) -> Self {
+        self.init_timeout = Some(timeout);
+        self
+    }
+
+    pub fn spawn(self) -> Link<A> {
+        let (tx, rx) = A::Channel::create(A::mailbox_capacity());
+        let token = self.token.clone().unwrap_or_default();
+        let system = self.system.clone();
+
+        let timed = self.queue_time.is_some();
+        let mut link: Link<A> = Link::create(tx, token.clone(), A::state(&self.spec), timed);
+        if self.passivate.is_some() {
+            let _ = link.state.parking.set(Parking::new(&token));
+        }
+        let worker = self.worker(&link, rx, token);
+
+        let exit = link.state.exit.clone();
+        let handle = tokio::spawn(async move {
+            // A passivated actor reports its exit from the task it is woken in.
+            if let Some(reason) = worker.await {
+                exit.send_replace(Some(reason));
+            }
+        });
+
+        link.set_monitor(handle);
+        if let Some(system) = &system {
+            system.register(&link);
+        }
+        link
+    }
+
+    /// Sets up one instance of the actor, reading from `rx`. The returned
+    /// future drives it from `init` to `terminate`, or until it parks once
+    /// passivated.
+    fn worker(
+        self,
+        link: &Link<A>,
+        rx: <A::Channel as ActorChannel>::Receiver,
+        token: CancelToken<A::Cancel>,
+    ) -> impl Future<Output = Option<ActorExit<A::Cancel>>> + Send + 'static {
+        let mut futures = JoinSet::default();
+        let mut children = Children::new(self.system.as_ref().map(ActorSystem::downgrade));
+        let mut receive_timeout = None;
+        let mut behavior = None;
+
+        let weak = link.downgrade();
+        let span = A::span(&self.spec);
+
+        let init = A::init(Init {
+            spec: self.spec,
+            token: token.clone(),
+            tasks: &mut futures,
+            children: &mut children,
+            receive_timeout: &mut receive_timeout,
+            behavior: &mut behavior,
+            link: link.clone(),
+        })
+        .boxed();
+
+        let ctx = ActorContext {
+            rx,
+            token,
+            futures,
+            span: span.clone(),
+            link: weak,
+            children,
+            restart: None,
+            isolate: self
+                .catch_panics
+                .then_some(A::on_handler_panic as Isolate<A>),
+            queue_time: self.queue_time,
+            passivate: self.passivate,
+            passivating: false,
+            receive_timeout,
+            stash: Default::default(),
+            unstashed: Default::default(),
+            behavior,
+        };
+
+        let lifecycle = Lifecycle {
+            respawn: self.respawn,
+            crash: self.crash,
+            escalate: self.escalate,
+            restarts: self.restarts.restarts(),
+            init_timeout: self.init_timeout,
+            init_retries: self.init_retry.as_ref().map(RestartPolicy::restarts),
+            ready: link.state.ready.clone(),
+            exit: link.state.exit.clone(),
+            system: self.system.as_ref().map(ActorSystem::downgrade),
+            parking: link.state.parking.get().cloned(),
+        };
+        let counts = lifecycle.counts();
+
+        async move {
+            // Keep the live-instance counters alive for as long as the task runs.
+            let reason = settle(lifecycle.run(ctx, init, None).await).await;
+            drop(counts);
+            reason
+        }
+        .instrument(span)
+    }
+}
+
+impl<A: Actor> Spawner<A>
+where
+    A::Spec: Clone,
+{
+    /// Keep a copy of the spec so the actor can be re-initialised behind the
+    /// same link, as [`CrashPolicy::Restart`] does.
+    pub fn restartable(mut self) -> Self {
+        let spec = self.spec.clone();
+        self.respawn = Some(Box::new(move || spec.clone()));
+        self
+    }
+
+    /// Retry a failed or timed out `init` from a copy of the spec, paced by
+    /// `policy`; it gives up once the policy's limit is exceeded.
+    pub fn init_retry(mut self, policy: RestartPolicy) -> Self {
+        self.init_retry = Some(policy);
+        self.restartable()
+    }
+
+    /// Passivate the actor after `idle` without messages.
+    ///
+    /// A passivated actor runs [`terminate`](Actor::terminate), with
+    /// [`ActorContext::is_passivating`] set, and drops its state, background
+    /// tasks, children and context; its tokio task ends and it no longer
+    /// counts as live. Its mailbox and links stay valid. The next message
+    /// that has not [expired](crate::Link::tell_dyn_with_ttl) spawns a new
+    /// task, which re-runs [`init`](Actor::init) from a copy of the spec and
+    /// then delivers the message to the new instance. Only the default
+    /// [`cycle`](Actor::cycle) tracks idleness, and instances of a
+    /// [`pool`](Self::pool) keep their task while passivated.
+    pub fn passivate_after(mut self, idle: Duration) -> Self {
+        self.passivate = Some(idle);
+        self.restartable()
+    }
+
+    /// Spawn an [`AutoscalePool`] of the actor, scaled by `policy`.
+    ///
+    /// Every instance is initialised from a copy of the spec and gets the
+    /// builder's settings. Under a [`parent`](Self::parent) or in a
+    /// [`system`](Self::system), each instance gets its own
+    /// [`child`](CancelToken::child) of that token.
+    pub fn autoscale(self, policy: AutoscalePolicy) -> AutoscalePool<A> {
+        AutoscalePool::start(self, policy)
+    }
+
+    /// A builder for one instance of a pool: same settings, own copy of the
+    /// spec.
+    fn replica(&self) -> Self {
+        let respawn = self.respawn.is_some().then(|| {
+            let spec = self.spec.clone();
+            Box::new(move || spec.clone()) as Respawn<A>
+        });
+        Self {
+            spec: self.spec.clone(),
+            token: None,
+            respawn,
+            crash: self.crash,
+            escalate: self.escalate.clone(),
+            restarts: self.restarts.clone(),
+            system: self.system.clone(),
+            init_timeout: self.init_timeout,
+            init_retry: self.init_retry.clone(),
+            passivate: self.passivate,
+            catch_panics: self.catch_panics,
+            queue_time: self.queue_time.clone(),
+        }
+    }
+
+    /// A builder for one instance of an [`AutoscalePool`]: a replica under
+    /// its own child of the builder's token.
+    pub(crate) fn member(&self) -> Self {
+        let mut member = self.replica();
+        member.token = self.token.as_ref().map(CancelToken::child);
+        member
+    }
+}
+
+impl<A: Actor> Spawner<A>
+where
+    A::Spec: Clone,
+    <A::Channel as ActorChannel>::Receiver: Clone,
+{
+    /// Spawn `size` instances of the actor sharing one mailbox, behind a
+    /// single link.
+    ///
+    /// The actor's channel must have a cloneable receiver, such as
+    /// [`SharedChannel`](crate::SharedChannel). Each message goes to
+    /// whichever instance is idle first, so one slow message does not hold
+    /// up the rest. Every instance is initialised from a copy of the spec,
+    /// gets the builder's settings and a [`child`](CancelToken::child) of the
+    /// pool's token: cancelling the link stops them all, while an instance
+    /// that stops on its own leaves the others running.
+    ///
+    /// The link reports [`ready`](Link::ready) once the first instance is
+    /// initialised. [`join`](Link::join) waits for every instance and
+    /// reports the first one that failed, or the last clean exit.
+    pub fn pool(self, size: usize) -> Link<A> {
+        assert!(size > 0, "a pool needs at least one instance");
+
+        let (tx, rx) = A::Channel::create(A::mailbox_capacity());
+        let token = self.token.clone().unwrap_or_default();
+        let system = self.system.clone();
+
+        let timed = self.queue_time.is_some();
+        let mut link: Link<A> = Link::create(tx, token.clone(), A::state(&self.spec), timed);
+        let mut workers = JoinSet::new();
+        for _ in 0..size {
+            let worker = self.replica().worker(&link, rx.clone(), token.child());
+            workers.spawn(worker);
+        }
+        drop(rx);
+
+        let exit = link.state.exit.clone();
+        let handle = tokio::spawn(async move {
+            let mut reason: Option<ActorExit<A::Cancel>> = None;
+            while let Some(worker) = workers.join_next().await {
+                // Pool instances have no parking, so they always report an exit.
+                let worker = worker.ok().flatten().unwrap_or(ActorExit::Aborted);
+                if reason.as_ref().is_none_or(ActorExit::is_clean) {
+                    reason = Some(worker);
+                }
+            }
+            exit.send_replace(reason);
+        });
+
+        link.set_monitor(handle);
+        if let Some(system) = &system {
+            system.register(&link);
+        }
+        link
+    }
+}
+
+/// Applies the actor's [`Terminate`] strategy once its cycle has stopped.
+///
+/// For [`Terminate::ProcessAll`] the mailbox is closed to new sends and every
+/// message already queued is handled before `terminate` runs.
+async fn finish<A: Actor>(
+    state: &mut A,
+    ctx: &mut ActorContext<A>,
+) -> Result<(), Box<dyn Any + Send>> {
+    match state.termination_strategy() {
+        Terminate::Exit => Ok(()),
+        Terminate::ProcessAll => {
+            let drain = async {
+                ctx.rx.close();
+                while let Some(msg) = ctx.unstashed.pop_front() {
+                    if let Some(msg) = msg.unless_expired() {
+                        A::handle(state, Exec::new(ctx), msg).await;
+                    }
+                }
+                while let Some(msg) = ctx.rx.recv().await {
+                    if let Some(msg) = msg.unless_expired() {
+                        A::handle(state, Exec::new(ctx), msg).await;
+                    }
+                }
+            };
+            AssertUnwindSafe(drain.in_current_span())
+                .catch_unwind()
+                .await
+        }
+    }
+}
+
+/// Drives one actor from `init` to `terminate`, applying its crash policy.
+///
+/// Panics are caught per phase (init, each `cycle`, terminate) rather than
+/// around the whole lifecycle, so the mailbox survives a crash and a restart
+/// can pick up where the previous incarnation stopped.
+pub(crate) struct Lifecycle<A: Actor> {
+    respawn: Option<Respawn<A>>,
+    crash: CrashPolicy,
+    escalate: Option<Escalation>,
+    restarts: Restarts,
+    init_timeout: Option<Duration>,
+    init_retries: Option<Restarts>,
+    ready: watch::Sender<bool>,
+    /// Where the task a passivated actor is woken in reports its exit.
+    pub(crate) exit: ExitSender<A::Cancel>,
+    pub(crate) system: Option<WeakSystem>,
+    /// Set for an actor that can passivate without keeping its task.
+    pub(crate) parking: Option<Arc<Parking>>,
+}
+
+impl<A: Actor> Lifecycle<A> {
+    /// Counts one live instance, globally and in the actor's system.
+    pub(crate) fn counts(&self) -> (Count<A>, Option<Count<A>>) {
+        let system = self.system.as_ref().and_then(WeakSystem::upgrade);
+        (
+            Count::new(),
+            system.map(|system| Count::new_in(system.stats())),
+        )
+    }
+
+    /// Runs the actor until it exits or is passivated. `pending` is the
+    /// message that woke a passivated actor, delivered right after init.
+    pub(crate) async fn run(
+        mut self,
+        mut ctx: ActorContext<A>,
+        mut init: InitResult<A>,
+        mut pending: Option<A::Message>,
+    ) -> Stopped<A> {
+        'incarnation: loop {
+            let caught = AssertUnwindSafe(init.in_current_span()).catch_unwind();
+            let initialized = match self.init_timeout {
+                Some(timeout) => tokio::time::timeout(timeout, caught).await,
+                None => Ok(caught.await),
+            };
+
+            let outcome = match initialized {
+                Ok(Ok(Ok(state))) => Ok(state),
+                Ok(Ok(Err(cancel))) => {
+                    tracing::error!(reason = ?cancel, "Actor terminated before initialization");
+                    Err(ActorExit::InitFailed(cancel))
+                }
+                Err(_) => {
+                    tracing::error!("Actor {} timed out during initialization", type_name::<A>());
+                    Err(ActorExit::InitTimedOut)
+                }
+                Ok(Err(payload)) => match self.crashed(&mut ctx, payload).await {
+                    Ok(next) => {
+                        init = next;
+                        continue 'incarnation;
+                    }
+                    Err(crash) => return Stopped::Exited(ActorExit::Crashed(crash)),
+                },
+            };
+
+            let mut state = match outcome {
+                Ok(state) => state,
+                Err(exit) => {
+                    if let Some(next) = self.retry_init(&mut ctx).await {
+                        init = next;
+                        continue 'incarnation;
+                    }
+                    let reason = match &exit {
+                        ActorExit::InitFailed(cancel) => cancel.clone(),
+                        _ => A::Cancel::default(),
+                    };
+                    ctx.token.cancel(reason);
+                    ctx.children.stop().await;
+                    return Stopped::Exited(exit);
+                }
+            };
+            self.ready.send_replace(true);
+
+            let reason = loop {
+                // The wake-up message may have expired while `init` ran.
+                let cycle = match pending
+                    .take()
+                    .and_then(|msg: A::Message| msg.unless_expired())
+                {
+                    Some(msg) => Either::Left(
+                        A::handle(&mut state, Exec::new(&mut ctx), msg).map(ControlFlow::Continue),
+                    ),
+                    None => Either::Right(A::cycle(&mut state, &mut ctx)),
+                };
+                let stopped = match AssertUnwindSafe(cycle.in_current_span())
+                    .catch_unwind()
+                    .await
+                {
+                    Ok(ControlFlow::Continue(_)) => {
+                        if let Some(crash) = ctx.restart.take() {
+                            drop(state);
+                            let restart = self.restart(&mut ctx, &crash).await;
+                            match self.resume(&mut ctx, restart, crash).await {
+                                Ok(next) => {
+                                    init = next;
+                                    continue 'incarnation;
+                                }
+                                Err(crash) => return Stopped::Exited(ActorExit::Crashed(crash)),
+                            }
+                        }
+
+                        if ctx.passivating {
+                            if let Err(exit) = self.passivate(state, &mut ctx).await {
+                                return Stopped::Exited(exit);
+                            }
+                            let idle = ctx.passivate.unwrap_or_default();
+                            return Stopped::Idle(Parked::new(self, ctx, idle));
+                        }
+
+                        continue;
+                    }
+                    Ok(ControlFlow::Break(reason)) => {
+                        finish(&mut state, &mut ctx).await.map(|()| reason)
+                    }
+                    Err(payload) => Err(payload),
+                };
+
+                match stopped {
+                    Ok(reason) => break reason,
+                    Err(payload) => {
+                        drop(state);
+                        match self.crashed(&mut ctx, payload).await {
+                            Ok(next) => {
+                                init = next;
+                                continue 'incarnation;
+                            }
+                            Err(crash) => return Stopped::Exited(ActorExit::Crashed(crash)),
+                        }
+                    }
+                }
+            };
+
+            ctx.children.stop().await;
+
+            let terminate = Actor::terminate(state, ctx, reason.clone()).in_current_span();
+            let exit = match AssertUnwindSafe(terminate).catch_unwind().await {
+                Ok(()) => {
+                    tracing::info!("Actor {} completed gracefully", type_name::<A>());
+                    ActorExit::Completed(reason)
+                }
+                Err(payload) => self.terminate_crashed(payload).await,
+            };
+            return Stopped::Exited(exit);
+        }
+    }
+
+    /// A panic in `terminate` stops the actor for good: the context is gone
+    /// with it, so there is nothing left to restart.
+    async fn terminate_crashed(&mut self, payload: Box<dyn Any + Send>) -> ActorExit<A::Cancel> {
+        let crash = Self::report(payload).await;
+        match self.crash {
+            CrashPolicy::Abort => std::process::exit(-1),
+            CrashPolicy::Escalate => self.escalate(crash.clone()),
+            CrashPolicy::Stop | CrashPolicy::Restart => {}
+        }
+        ActorExit::Crashed(crash)
+    }
+
+    /// Terminates the idle actor but keeps its mailbox.
+    async fn passivate(
+        &mut self,
+        state: A,
+        ctx: &mut ActorContext<A>,
+    ) -> Result<(), ActorExit<A::Cancel>> {
+        tracing::debug!("Passivating idle actor {}", type_name::<A>());
+        ctx.children.stop().await;
+
+        // `terminate` consumes its context, so hand it one without the mailbox.
+        let (_, closed) = A::Channel::create(1);
+        let detached = ActorContext {
+            rx: closed,
+            token: ctx.token.clone(),
+            futures: std::mem::take(&mut ctx.futures),
+            span: ctx.span.clone(),
+            link: ctx.link.clone(),
+            children: Children::default(),
+            restart: None,
+            isolate: ctx.isolate,
+            queue_time: ctx.queue_time.clone(),
+            passivate: ctx.passivate,
+            passivating: true,
+            receive_timeout: None,
+            stash: Default::default(),
+            unstashed: Default::default(),
+            behavior: None,
+        };
+
+        let terminate = A::terminate(state, detached, CancelReason::default()).in_current_span();
+        if let Err(payload) = AssertUnwindSafe(terminate).catch_unwind().await {
+            ctx.token.cancel(A::Cancel::default());
+            return Err(self.terminate_crashed(payload).await);
+        }
+        Ok(())
+    }
+
+    /// Logs a panic and passes it to the actor's crash hooks.
+    async fn report(payload: Box<dyn Any + Send>) -> Crash {
+        let crash = Crash::new::<A>(&*payload);
+        tracing::error!("Actor {} crashed: {}", crash.actor, crash.message);
+        #[allow(deprecated)]
+        A::crash(payload).await;
+        A::on_crash(&crash).await;
+        crash
+    }
+
+    /// Applies the crash policy. Returns the next `init` if the actor should
+    /// be restarted in place, or gives the crash back if it should stop.
+    async fn crashed(
+        &mut self,
+        ctx: &mut ActorContext<A>,
+        payload: Box<dyn Any + Send>,
+    ) -> Result<InitResult<A>, Crash> {
+        let crash = Self::report(payload).await;
+
+        let restart = match self.crash {
+            CrashPolicy::Abort => std::process::exit(-1),
+            CrashPolicy::Stop => None,
+            CrashPolicy::Escalate => {
+                self.escalate(crash.clone());
+                None
+            }
+            CrashPolicy::Restart => self.restart(ctx, &crash).await,
+        };
+
+        self.resume(ctx, restart, crash).await
+    }
+
+    /// Goes on with the restarted actor, or stops it for good if it could not
+    /// be restarted.
+    async fn resume(
+        &mut self,
+        ctx: &mut ActorContext<A>,
+        restart: Option<InitResult<A>>,
+        crash: Crash,
+    ) -> Result<InitResult<A>, Crash> {
+        match restart {
+            Some(init) => Ok(init),
+            None => {
+                // Take down everything spawned under this actor.
+                ctx.token.cancel(A::Cancel::default());
+                ctx.children.stop().await;
+                Err(crash)
+            }
+        }
+    }
+
+    async fn restart(&mut self, ctx: &mut ActorContext<A>, crash: &Crash) -> Option<InitResult<A>> {
+        if self.respawn.is_none() {
+            tracing::warn!(
+                "Actor {} cannot be restarted without `Spawner::restartable`, stopping",
+                crash.actor
+            );
+            return None;
+        }
+
+        let Some(delay) = self.restarts.record() else {
+            tracing::error!("Actor {} restarted too often, escalating", crash.actor);
+            self.escalate(crash.clone());
+            return None;
+        };
+
+        tokio::select! {
+            _ = ctx.token.cancelled() => return None,
+            _ = tokio::time::sleep(delay) => {}
+        }
+
+        tracing::info!("Restarting actor {}", crash.actor);
+        self.reinit(ctx).await
+    }
+
+    /// Applies the init retry policy, if any. Returns the next `init`, or
+    /// `None` if the actor should give up.
+    async fn retry_init(&mut self, ctx: &mut ActorContext<A>) -> Option<InitResult<A>> {
+        let retries = self.init_retries.as_mut()?;
+        let Some(delay) = retries.record() else {
+            tracing::error!(
+                "Actor {} failed to initialize too often, giving up",
+                type_name::<A>()
+            );
+            return None;
+        };
+
+        tokio::select! {
+            _ = ctx.token.cancelled() => return None,
+            _ = tokio::time::sleep(delay) => {}
+        }
+
+        tracing::info!("Retrying initialization of actor {}", type_name::<A>());
+        self.reinit(ctx).await
+    }
+
+    /// Starts a new incarnation from a fresh copy of the spec.
+    pub(crate) async fn reinit(&mut self, ctx: &mut ActorContext<A>) -> Option<InitResult<A>> {
+        let respawn = self.respawn.as_mut()?;
+
+        // Nobody is left to talk to a new incarnation.
+        let link = ctx.link.upgrade()?;
+
+        // Tasks and children of the previous incarnation die with it.
+        ctx.futures.abort_all();
+        ctx.children.stop().await;
+        ctx.receive_timeout = None;
+        ctx.behavior = None;
+
+        // The new incarnation gets to handle what the old one put aside.
+        ctx.unstash_all();
+
+        let init = A::init(Init {
+            spec: respawn(),
+            token: ctx.token.clone(),
+            tasks: &mut ctx.futures,
+            children: &mut ctx.children,
+            receive_timeout: &mut ctx.receive_timeout,
+            behavior: &mut ctx.behavior,
+            link,
+        });
+
+        Some(init.boxed())
+    }
+
+    fn escalate(&self, crash: Crash) {
+        match &self.escalate {
+            Some(escalate) => escalate(crash),
+            None => tracing::warn!("Actor {} has no parent to escalate to", crash.actor),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+use std::sync::Weak;
+
+use parking_lot::Mutex;
+
+use crate::actor::Actor;
+use crate::cancel::CancelToken;
+use crate::children::Children;
+use crate::count::Registry;
+use crate::link::Link;
+use crate::spawn::Spawner;
+
+/// A group of actors that share a root [`CancelToken`] and are shut down
+/// together.
+///
+/// Every actor spawned through the system, including the children those
+/// actors spawn with [`ActorContext::spawn_child`](crate::ActorContext::spawn_child),
+/// is cancelled when the system is, and counted in the system's own
+/// [`Registry`] in addition to the global [`count`](crate::count) map, so
+/// separate systems (e.g. one per test) don't see each other's actors.
+///
+/// `ActorSystem` is a cheap handle; clones refer to the same system. Dropping
+/// the last handle cancels every actor still running in it.
+#[derive(Clone, Default)]
+pub struct ActorSystem {
+    inner: Arc<SystemInner>,
+}
+
+#[derive(Default)]
+struct SystemInner {
+    token: CancelToken<()>,
+    actors: Mutex<Children>,
+    stats: Registry,
+}
+
+impl ActorSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawns an actor in this system.
+    ///
+    /// Use [`Spawner::system`] to combine this with other spawn options.
+    pub fn spawn<A: Actor>(&self, spec: A::Spec) -> Link<A> {
+        Spawner::new(spec).system(self).spawn()
+    }
+
+    /// The root token every actor in the system is spawned under.
+    pub fn token(&self) -> &CancelToken<()> {
+        &self.inner.token
+    }
+
+    /// Live-instance statistics for the actors of this system.
+    pub fn stats(&self) -> &Registry {
+        &self.inner.stats
+    }
+
+    /// Number of actors in the system that have not finished yet.
+    pub fn len(&self) -> usize {
+        self.inner.actors.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Cancels every actor in the system at once, without waiting.
+    pub fn cancel(&self) {
+        self.inner.token.cancel(())
+    }
+
+    /// Stops the actors one at a time, newest first, waiting for each to
+    /// finish before stopping the next, then cancels the root token.
+    ///
+    /// Actors spawned later may depend on earlier ones, so this lets e.g. a
+    /// client stop before the connection pool it uses.
+    pub async fn shutdown(&self) {
+        loop {
+            let mut actors = std::mem::take(&mut *self.inner.actors.lock());
+            if actors.is_empty() {
+                break;
+            }
+            actors.stop().await;
+        }
+        self.cancel();
+    }
+
+    pub(crate) fn register<A: Actor>(&self, link: &Link<A>) {
+        self.inner.actors.lock().push(link);
+    }
+
+    pub(crate) fn downgrade(&self) -> WeakSystem {
+        WeakSystem(Arc::downgrade(&self.inner))
+    }
+}
+
+/// A handle to an [`ActorSystem`] that does not keep it alive, held by the
+/// system's actors so that they can spawn children into it.
+#[derive(Clone)]
+pub(crate) struct WeakSystem(Weak<SystemInner>);
+
+impl WeakSystem {
+    pub(crate) fn upgrade(&self) -> Option<ActorSystem> {
+        self.0.upgrade().map(|inner| ActorSystem { inner })
+    }
+}
+
+impl Drop for SystemInner {
+    fn drop(&mut self) {
+        self.token.cancel(())
+    }
+}
@@ -1,12 +1,16 @@
+use std::future::Future;
 use std::hash::Hash;
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::FutureExt;
 use futures::future::BoxFuture;
+use futures::future::Either;
 use tokio::sync::oneshot::error::RecvError;
 
 use crate::Link;
 use crate::actor::Actor;
+use crate::ask::Ask;
 use crate::envelope::Envelope;
 use crate::error::ActorError;
 use crate::error::ActorSendError;
@@ -14,10 +18,16 @@ use crate::error::FromError;
 use crate::handler::Handler;
 use crate::link::ActorLike;
 use crate::link::LinkState;
+use crate::link::reply;
+use crate::monitor::ActorExit;
+use crate::monitor::ExitReceiver;
+use crate::monitor::Shutdown;
+use crate::monitor::exited;
 use crate::multi::Multi;
 
 pub struct WeakLink<A: ActorLike> {
     state: std::sync::Weak<LinkState<A>>,
+    pub(crate) exit: ExitReceiver<A::Cancel>,
 }
 
 impl<A: ActorLike> Hash for WeakLink<A> {
@@ -38,6 +48,7 @@ impl<A: Actor> Clone for WeakLink<A> {
     fn clone(&self) -> Self {
         Self {
             state: self.state.clone(),
+            exit: self.exit.clone(),
         }
     }
 }
@@ -59,6 +70,23 @@ impl<A: Actor> WeakLink<A> {
         }
     }
 
+    /// Like [`Link::shutdown`]; an actor that is already gone counts as
+    /// shut down gracefully.
+    pub async fn shutdown(&self, reason: A::Cancel, grace: Duration) -> Shutdown {
+        match self.upgrade() {
+            Some(link) => link.shutdown(reason, grace).await,
+            None => Shutdown::Graceful,
+        }
+    }
+
+    /// Like [`Link::join`], but also works after the last link is gone.
+    pub fn join(&self) -> impl Future<Output = ActorExit<A::Cancel>> + Send + 'static {
+        match self.upgrade() {
+            Some(link) => Either::Left(link.join()),
+            None => Either::Right(exited(self.exit.clone())),
+        }
+    }
+
     // ========== EXISTING API - PRESERVED FOR BACKWARD COMPATIBILITY ==========
 
     pub async fn ask_dyn_async<T>(&self, message: T) -> BoxFuture<'static, <A as Handler<T>>::Reply>
@@ -126,10 +154,52 @@ impl<A: Actor> WeakLink<A> {
     }
 }
 
+impl<A: Actor> WeakLink<A> {
+    /// Like [`Link::ask`]; resolves to [`ActorError::Dead`] if the actor is
+    /// gone.
+    pub fn ask<T>(&self, message: T) -> Ask<'static, <A as Handler<T>>::Reply>
+    where
+        T: Send + Sync + 'static,
+        A: Handler<T>,
+        A: ActorLike<Message = Multi<A>>,
+    {
+        let link = self.upgrade();
+        let request = async move {
+            match link {
+                Some(link) => link.post(message).await.map(|rx| reply(rx).boxed()),
+                None => Err(<A as Handler<T>>::Reply::from_err(ActorError::Dead)),
+            }
+        };
+        Ask::new(request.boxed(), <A as Actor>::ask_timeout())
+    }
+
+    /// Like [`Link::request`]; resolves to [`ActorError::Dead`] if the actor
+    /// is gone.
+    pub fn request<T, R>(&self, message: T) -> Ask<'static, R>
+    where
+        A: Actor<Message = Envelope<T, R>>,
+        T: Send + Sync + 'static,
+        R: Send + Sync + 'static,
+        R: FromError<ActorSendError<A>>,
+        R: FromError<RecvError>,
+        R: FromError<ActorError>,
+    {
+        let link = self.upgrade();
+        let request = async move {
+            match link {
+                Some(link) => link.post_envelope(message).await.map(|rx| reply(rx).boxed()),
+                None => Err(R::from_err(ActorError::Dead)),
+            }
+        };
+        Ask::new(request.boxed(), <A as Actor>::ask_timeout())
+    }
+}
+
 impl<A: Actor> Link<A> {
     pub fn downgrade(&self) -> WeakLink<A> {
         WeakLink {
             state: Arc::downgrade(&self.state),
+            exit: self.state.exit.subscribe(),
         }
     }
 }
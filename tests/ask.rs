@@ -0,0 +1,171 @@
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorError;
+use actor12::Call;
+use actor12::Envelope;
+use actor12::Exec;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+struct Sleeper;
+
+impl Actor for Sleeper {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Sleeper))
+    }
+}
+
+struct Impatient;
+
+impl Actor for Impatient {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn ask_timeout() -> Option<Duration> {
+        Some(Duration::from_millis(100))
+    }
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Impatient))
+    }
+}
+
+struct Sleep(u64);
+
+impl Handler<Sleep> for Sleeper {
+    type Reply = anyhow::Result<u64>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, msg: Sleep) -> Self::Reply {
+        tokio::time::sleep(Duration::from_millis(msg.0)).await;
+        Ok(msg.0)
+    }
+}
+
+impl Handler<Sleep> for Impatient {
+    type Reply = anyhow::Result<u64>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, msg: Sleep) -> Self::Reply {
+        tokio::time::sleep(Duration::from_millis(msg.0)).await;
+        Ok(msg.0)
+    }
+}
+
+struct Echo;
+
+impl Actor for Echo {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Envelope<u64, anyhow::Result<u64>>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Echo))
+    }
+
+    async fn handle(&mut self, _ctx: Exec<'_, Self>, msg: Self::Message) {
+        tokio::time::sleep(Duration::from_millis(msg.value)).await;
+        let _ = msg.reply.send(Ok(msg.value));
+    }
+}
+
+fn is_timeout(err: &anyhow::Error) -> bool {
+    matches!(err.downcast_ref::<ActorError>(), Some(ActorError::Timeout(_)))
+}
+
+#[tokio::test(start_paused = true)]
+async fn ask_times_out() {
+    let link = actor12::spawn::<Sleeper>(());
+
+    let err = link
+        .ask(Sleep(1000))
+        .timeout(Duration::from_millis(10))
+        .await
+        .unwrap_err();
+    assert!(is_timeout(&err));
+
+    // The handler still finishes the first request before this one.
+    let reply = link.ask(Sleep(10)).timeout(Duration::from_secs(2)).await;
+    assert_eq!(reply.unwrap(), 10);
+}
+
+#[tokio::test(start_paused = true)]
+async fn actor_default_timeout_applies_to_every_ask() {
+    let link = actor12::spawn::<Impatient>(());
+
+    assert!(is_timeout(&link.ask_dyn(Sleep(1000)).await.unwrap_err()));
+    assert!(is_timeout(&link.ask_dyn_async(Sleep(1000)).await.await.unwrap_err()));
+    assert!(is_timeout(&link.downgrade().ask_dyn(Sleep(1000)).await.unwrap_err()));
+    assert!(is_timeout(&link.ask(Sleep(1000)).await.unwrap_err()));
+
+    assert_eq!(link.ask(Sleep(1000)).no_timeout().await.unwrap(), 1000);
+}
+
+#[tokio::test(start_paused = true)]
+async fn huge_timeouts_never_expire() {
+    let link = actor12::spawn::<Sleeper>(());
+    assert_eq!(link.ask(Sleep(1000)).timeout(Duration::MAX).await.unwrap(), 1000);
+
+    let link = actor12::spawn::<Impatient>(());
+    let reply = link.ask(Sleep(1000)).timeout(Duration::MAX).deferred().await;
+    assert_eq!(reply.await.unwrap(), 1000);
+}
+
+#[tokio::test(start_paused = true)]
+async fn deferred_ask_times_out() {
+    let link = actor12::spawn::<Sleeper>(());
+
+    let reply = link
+        .ask(Sleep(1000))
+        .timeout(Duration::from_millis(10))
+        .deferred()
+        .await;
+    assert!(is_timeout(&reply.await.unwrap_err()));
+}
+
+#[tokio::test(start_paused = true)]
+async fn request_times_out() {
+    let link = actor12::spawn::<Echo>(());
+
+    let err = link.request(1000).timeout(Duration::from_millis(10)).await.unwrap_err();
+    assert!(is_timeout(&err));
+    assert_eq!(link.request(5).await.unwrap(), 5);
+}
+
+#[tokio::test(start_paused = true)]
+async fn weak_and_dyn_links_ask() {
+    let link = actor12::spawn::<Sleeper>(());
+
+    let weak = link.downgrade();
+    let err = weak.ask(Sleep(1000)).timeout(Duration::from_millis(10)).await.unwrap_err();
+    assert!(is_timeout(&err));
+
+    let dynamic = link.to_dyn::<Sleep>();
+    let err = dynamic.ask(Sleep(1000)).timeout(Duration::from_millis(10)).await.unwrap_err();
+    assert!(is_timeout(&err));
+    dynamic.ask(Sleep(10)).await.unwrap();
+
+    drop((link, dynamic));
+    let err = weak.ask(Sleep(10)).await.unwrap_err();
+    assert!(matches!(err.downcast_ref::<ActorError>(), Some(ActorError::Dead)));
+}
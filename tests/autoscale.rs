@@ -0,0 +1,206 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorSystem;
+use actor12::AutoscalePolicy;
+use actor12::AutoscalePool;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Spawner;
+use actor12::prelude::InitFuture;
+use futures::future;
+use tokio::time::sleep;
+
+struct Ingest;
+
+impl Actor for Ingest {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Ingest))
+    }
+}
+
+/// Takes this long to handle.
+struct Work(Duration);
+
+impl Handler<Work> for Ingest {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, msg: Work) -> Self::Reply {
+        sleep(msg.0).await;
+        Ok(())
+    }
+}
+
+/// Takes a second to handle, then counts itself.
+struct Tally(Arc<AtomicU32>);
+
+impl Handler<Tally> for Ingest {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, msg: Tally) -> Self::Reply {
+        sleep(Duration::from_secs(1)).await;
+        msg.0.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+fn policy() -> AutoscalePolicy {
+    AutoscalePolicy {
+        min: 1,
+        max: 3,
+        scale_up_depth: 2.0,
+        scale_up_latency: None,
+        scale_down_depth: 0.0,
+        cooldown: Duration::from_secs(5),
+        interval: Duration::from_secs(1),
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn starts_with_min_instances() {
+    let pool = AutoscalePool::<Ingest>::spawn((), AutoscalePolicy { min: 2, ..policy() });
+    assert_eq!(pool.len(), 2);
+
+    sleep(Duration::from_secs(30)).await;
+    assert_eq!(pool.len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn grows_with_mailbox_depth_and_shrinks_when_idle() {
+    let pool = AutoscalePool::<Ingest>::spawn((), policy());
+    for _ in 0..20 {
+        pool.tell_dyn(Work(Duration::from_secs(1))).await;
+    }
+
+    sleep(Duration::from_millis(1500)).await;
+    assert_eq!(pool.len(), 2);
+
+    // No change within the cooldown.
+    sleep(Duration::from_secs(3)).await;
+    assert_eq!(pool.len(), 2);
+
+    sleep(Duration::from_secs(2)).await;
+    assert_eq!(pool.len(), 3);
+    let members = pool.router().members();
+
+    // Capped at max.
+    sleep(Duration::from_secs(5)).await;
+    assert_eq!(pool.len(), 3);
+
+    // Idle instances are retired one per cooldown, down to min.
+    sleep(Duration::from_secs(60)).await;
+    assert_eq!(pool.len(), 1);
+    assert_eq!(members.iter().filter(|link| link.alive()).count(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn grows_with_queue_latency() {
+    let policy = AutoscalePolicy {
+        scale_up_depth: f64::INFINITY,
+        scale_up_latency: Some(Duration::from_millis(500)),
+        ..policy()
+    };
+    let pool = AutoscalePool::<Ingest>::spawn((), policy);
+
+    // Asks that never queue up leave the pool alone.
+    for _ in 0..3 {
+        pool.ask_dyn(Work(Duration::from_secs(1))).await.unwrap();
+    }
+    assert_eq!(pool.len(), 1);
+
+    // Messages sent through the router are timed as well.
+    for _ in 0..3 {
+        pool.router().tell_dyn(Work(Duration::from_secs(1))).await;
+    }
+    sleep(Duration::from_millis(2500)).await;
+    assert_eq!(pool.len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn retired_instances_handle_their_queued_messages() {
+    let policy = AutoscalePolicy {
+        max: 2,
+        scale_up_depth: 4.0,
+        scale_down_depth: 3.0,
+        cooldown: Duration::from_secs(2),
+        ..policy()
+    };
+    let pool = AutoscalePool::<Ingest>::spawn((), policy);
+    let handled = Arc::new(AtomicU32::new(0));
+
+    for _ in 0..6 {
+        pool.tell_dyn(Tally(handled.clone())).await;
+    }
+    sleep(Duration::from_millis(1500)).await;
+    let members = pool.router().members();
+    assert_eq!(members.len(), 2);
+    for _ in 0..6 {
+        members[1].tell_dyn(Tally(handled.clone())).await;
+    }
+
+    // The oldest instance is retired while it still has queued messages.
+    sleep(Duration::from_secs(30)).await;
+    assert_eq!(pool.len(), 1);
+    assert!(!members[0].alive());
+    assert_eq!(handled.load(Ordering::SeqCst), 12);
+}
+
+#[tokio::test(start_paused = true)]
+async fn retired_instances_take_messages_routed_before_removal() {
+    let pool = AutoscalePool::<Ingest>::spawn((), policy());
+    for _ in 0..4 {
+        pool.tell_dyn(Work(Duration::from_secs(1))).await;
+    }
+    sleep(Duration::from_millis(1500)).await;
+    let members = pool.router().members();
+    assert_eq!(members.len(), 2);
+
+    while pool.len() == 2 {
+        sleep(Duration::from_millis(1)).await;
+    }
+    let current = pool.router().members();
+    let retired = members.iter().find(|link| !current.contains(link)).unwrap();
+
+    // A sender that picked the instance just before it left the router.
+    assert!(retired.ask_dyn(Work(Duration::ZERO)).await.is_ok());
+
+    sleep(Duration::from_secs(2)).await;
+    assert!(!retired.alive());
+    assert!(retired.join().await.is_clean());
+}
+
+#[tokio::test(start_paused = true)]
+async fn replaces_stopped_instances() {
+    let pool = AutoscalePool::<Ingest>::spawn((), AutoscalePolicy { min: 2, ..policy() });
+
+    let members = pool.router().members();
+    members[0].cancel_and_wait(()).await;
+
+    sleep(Duration::from_millis(1500)).await;
+    assert_eq!(pool.len(), 2);
+    assert!(pool.router().members().iter().all(|link| link.alive()));
+}
+
+#[tokio::test(start_paused = true)]
+async fn instances_get_the_spawner_settings() {
+    let system = ActorSystem::new();
+    let pool = Spawner::<Ingest>::new(()).system(&system).autoscale(policy());
+    assert_eq!(system.len(), 1);
+
+    system.shutdown().await;
+    assert!(pool.router().members().iter().all(|link| !link.alive()));
+}
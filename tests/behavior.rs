@@ -0,0 +1,132 @@
+use actor12::Actor;
+use actor12::ActorError;
+use actor12::Behavior;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Unhandled;
+use actor12::prelude::InitFuture;
+use futures::FutureExt;
+use futures::future;
+
+struct Connection {
+    queries: u32,
+}
+
+impl Connection {
+    fn disconnected() -> Behavior<Self> {
+        Behavior::new("disconnected")
+            .handle::<Connect>()
+            .handle::<Phase>()
+            .unhandled(Unhandled::Stash)
+    }
+
+    fn connected() -> Behavior<Self> {
+        Behavior::new("connected")
+            .handle::<Query>()
+            .handle::<Disconnect>()
+            .handle::<Phase>()
+            .on::<Connect>(|_, _, _| async { Err(anyhow::anyhow!("already connected")) }.boxed())
+    }
+
+    fn closed() -> Behavior<Self> {
+        Behavior::new("closed").handle::<Phase>()
+    }
+}
+
+impl Actor for Connection {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(mut init: Init<'_, Self>) -> impl InitFuture<Self> {
+        init.set_behavior(Self::disconnected());
+        future::ready(Ok(Connection { queries: 0 }))
+    }
+}
+
+struct Connect;
+struct Disconnect;
+struct Query(u32);
+struct Phase;
+
+impl Handler<Connect> for Connection {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, mut ctx: Call<'_, Self, Self::Reply>, _: Connect) -> Self::Reply {
+        ctx.set_behavior(Self::connected());
+        Ok(())
+    }
+}
+
+impl Handler<Disconnect> for Connection {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, mut ctx: Call<'_, Self, Self::Reply>, _: Disconnect) -> Self::Reply {
+        ctx.set_behavior(Self::closed());
+        Ok(())
+    }
+}
+
+impl Handler<Query> for Connection {
+    type Reply = anyhow::Result<u32>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, query: Query) -> Self::Reply {
+        self.queries += 1;
+        Ok(query.0 * 10)
+    }
+}
+
+impl Handler<Phase> for Connection {
+    type Reply = anyhow::Result<Option<&'static str>>;
+
+    async fn handle(&mut self, ctx: Call<'_, Self, Self::Reply>, _: Phase) -> Self::Reply {
+        Ok(ctx.behavior())
+    }
+}
+
+#[tokio::test]
+async fn unhandled_messages_wait_for_the_next_behavior() {
+    let link = actor12::spawn::<Connection>(());
+    assert_eq!(link.ask_dyn(Phase).await.unwrap(), Some("disconnected"));
+
+    let first = link.ask_dyn_async(Query(1)).await;
+    let second = link.ask_dyn_async(Query(2)).await;
+    link.ask_dyn(Connect).await.unwrap();
+
+    assert_eq!(first.await.unwrap(), 10);
+    assert_eq!(second.await.unwrap(), 20);
+    assert_eq!(link.ask_dyn(Phase).await.unwrap(), Some("connected"));
+}
+
+#[tokio::test]
+async fn behavior_handler_replaces_the_actor_handler() {
+    let link = actor12::spawn::<Connection>(());
+    link.ask_dyn(Connect).await.unwrap();
+
+    let err = link.ask_dyn(Connect).await.unwrap_err();
+    assert_eq!(err.to_string(), "already connected");
+    assert_eq!(link.ask_dyn(Phase).await.unwrap(), Some("connected"));
+}
+
+#[tokio::test]
+async fn unhandled_messages_are_rejected() {
+    let link = actor12::spawn::<Connection>(());
+    link.ask_dyn(Connect).await.unwrap();
+    link.ask_dyn(Disconnect).await.unwrap();
+
+    let err = link.ask_dyn(Query(1)).await.unwrap_err();
+    match err.downcast_ref::<ActorError>() {
+        Some(ActorError::Unhandled { behavior, message }) => {
+            assert_eq!(*behavior, "closed");
+            assert!(message.ends_with("Query"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
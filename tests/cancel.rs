@@ -0,0 +1,80 @@
+use actor12::Actor;
+use actor12::ActorExit;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Spawner;
+use actor12::cancel::CancelToken;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+/// Enough siblings for the parent to prune its children at least once.
+const SIBLINGS: usize = 64;
+
+#[test]
+fn cancel_reaches_grandchildren_of_dropped_tokens() {
+    let root = CancelToken::<()>::new();
+    let mid = root.child();
+    let leaf = mid.child();
+    drop(mid);
+
+    // Creating children prunes unused nodes; `mid` must survive for `leaf`.
+    let _siblings: Vec<_> = (0..SIBLINGS).map(|_| root.child()).collect();
+    root.cancel(());
+    assert!(leaf.is_cancelled());
+}
+
+#[test]
+fn cancel_reaches_mapped_grandchildren_of_dropped_tokens() {
+    let root = CancelToken::<()>::new();
+    let mid = root.child_map(|_| "root");
+    let leaf = mid.child();
+    drop(mid);
+
+    let _siblings: Vec<_> = (0..SIBLINGS).map(|_| root.child_map(|_| "root")).collect();
+    root.cancel(());
+    assert_eq!(leaf.reason().map(|reason| *reason.value()), Some("root"));
+}
+
+#[test]
+fn many_short_lived_children_under_one_token() {
+    let root = CancelToken::<()>::new();
+    let kept: Vec<_> = (0..100_000)
+        .filter_map(|i| {
+            let child = root.child();
+            (i % 1000 == 0).then_some(child)
+        })
+        .collect();
+
+    root.cancel(());
+    assert!(kept.iter().all(CancelToken::is_cancelled));
+}
+
+struct Idle;
+
+impl Actor for Idle {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Idle))
+    }
+}
+
+#[tokio::test]
+async fn thousands_of_actors_under_one_token() {
+    let root = CancelToken::<()>::new();
+    let links: Vec<_> = (0..5000)
+        .map(|_| Spawner::<Idle>::new(()).parent(&root).spawn())
+        .collect();
+
+    root.cancel(());
+    for link in &links {
+        assert!(matches!(link.join().await, ActorExit::Completed(_)));
+    }
+}
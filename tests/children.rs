@@ -0,0 +1,197 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorContext;
+use actor12::ActorExit;
+use actor12::Call;
+use actor12::CrashPolicy;
+use actor12::Handler;
+use actor12::Init;
+use actor12::Link;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Spawner;
+use actor12::cancel::CancelReason;
+use actor12::cancel::CancelToken;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+type Log = Arc<Mutex<Vec<&'static str>>>;
+
+struct Child {
+    log: Log,
+}
+
+impl Actor for Child {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = Log;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Child { log: ctx.spec }))
+    }
+
+    async fn terminate(self, _ctx: ActorContext<Self>, _reason: CancelReason<Self::Cancel>) {
+        // Slow enough that the parent would finish first if it didn't wait.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        self.log.lock().unwrap().push("child");
+    }
+}
+
+/// Panics on [`Boom`].
+struct Fragile;
+
+impl Actor for Fragile {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Fragile))
+    }
+}
+
+struct Boom;
+
+impl Handler<Boom> for Fragile {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Boom) -> Self::Reply {
+        panic!("boom");
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+enum Stop {
+    #[default]
+    Dropped,
+    Requested,
+}
+
+struct Parent {
+    log: Log,
+    first: Link<Child>,
+}
+
+impl Actor for Parent {
+    type Cancel = Stop;
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = Log;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(mut ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        let first = ctx.spawn_child::<Child>(ctx.spec.clone());
+        future::ready(Ok(Parent {
+            log: ctx.spec,
+            first,
+        }))
+    }
+
+    async fn terminate(self, ctx: ActorContext<Self>, _reason: CancelReason<Self::Cancel>) {
+        assert!(ctx.children.is_empty());
+        assert!(!self.first.alive());
+        self.log.lock().unwrap().push("parent");
+    }
+}
+
+struct SpawnChild;
+struct SpawnEscalating;
+struct First;
+struct CountChildren;
+
+impl Handler<SpawnChild> for Parent {
+    type Reply = anyhow::Result<Link<Child>>;
+
+    async fn handle(&mut self, mut ctx: Call<'_, Self, Self::Reply>, _: SpawnChild) -> Self::Reply {
+        Ok(ctx.spawn_child::<Child>(self.log.clone()))
+    }
+}
+
+impl Handler<SpawnEscalating> for Parent {
+    type Reply = anyhow::Result<Link<Fragile>>;
+
+    async fn handle(&mut self, mut ctx: Call<'_, Self, Self::Reply>, _: SpawnEscalating) -> Self::Reply {
+        Ok(ctx.spawn_child_with(Spawner::new(()).crash_policy(CrashPolicy::Escalate)))
+    }
+}
+
+impl Handler<First> for Parent {
+    type Reply = anyhow::Result<Link<Child>>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: First) -> Self::Reply {
+        Ok(self.first.clone())
+    }
+}
+
+impl Handler<CountChildren> for Parent {
+    type Reply = anyhow::Result<usize>;
+
+    async fn handle(&mut self, ctx: Call<'_, Self, Self::Reply>, _: CountChildren) -> Self::Reply {
+        Ok(ctx.children.len())
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn children_stop_before_the_parent_terminates() {
+    let log = Log::default();
+    let parent = actor12::spawn::<Parent>(log.clone());
+
+    let first = parent.ask_dyn(First).await.unwrap();
+    let second = parent.ask_dyn(SpawnChild).await.unwrap();
+    assert_eq!(parent.ask_dyn(CountChildren).await.unwrap(), 2);
+
+    parent.cancel(Stop::Requested);
+    assert!(parent.join().await.is_clean());
+
+    assert_eq!(*log.lock().unwrap(), ["child", "child", "parent"]);
+    assert!(matches!(first.join().await, ActorExit::Completed(_)));
+    assert!(matches!(second.join().await, ActorExit::Completed(_)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn escalated_crashes_take_down_the_parent() {
+    let parent = actor12::spawn::<Parent>(Log::default());
+    let first = parent.ask_dyn(First).await.unwrap();
+
+    let fragile = parent.ask_dyn(SpawnEscalating).await.unwrap();
+    assert!(fragile.ask_dyn(Boom).await.is_err());
+
+    let exit = parent.join().await;
+    assert!(matches!(exit, ActorExit::Crashed(crash) if crash.message.ends_with("Fragile panicked: boom")));
+    assert!(!first.alive());
+}
+
+#[test]
+fn mapped_child_tokens_follow_their_parent() {
+    let parent = CancelToken::<Stop>::new();
+    let child = parent.child_map(|_| ());
+
+    parent.cancel(Stop::Requested);
+
+    assert!(child.is_cancelled());
+    assert!(parent.child_map(|_| ()).is_cancelled());
+}
+
+#[tokio::test(start_paused = true)]
+async fn finished_children_are_forgotten() {
+    let parent = actor12::spawn::<Parent>(Log::default());
+
+    let child = parent.ask_dyn(SpawnChild).await.unwrap();
+    child.cancel(());
+    child.join().await;
+
+    assert_eq!(parent.ask_dyn(CountChildren).await.unwrap(), 1);
+}
@@ -0,0 +1,99 @@
+use actor12::Actor;
+use actor12::ActorExit;
+use actor12::Call;
+use actor12::CrashPolicy;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Spawner;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+struct Fragile {
+    count: u32,
+}
+
+impl Actor for Fragile {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = u32;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Fragile { count: ctx.spec }))
+    }
+}
+
+struct Boom;
+struct Increment;
+
+impl Handler<Boom> for Fragile {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Boom) -> Self::Reply {
+        panic!("boom");
+    }
+}
+
+impl Handler<Increment> for Fragile {
+    type Reply = anyhow::Result<u32>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Increment) -> Self::Reply {
+        self.count += 1;
+        Ok(self.count)
+    }
+}
+
+#[tokio::test]
+async fn the_default_policy_stops_without_exiting_the_process() {
+    let link = actor12::spawn::<Fragile>(0);
+
+    assert!(link.ask_dyn(Boom).await.is_err());
+    let exit = link.join().await;
+    assert!(matches!(exit, ActorExit::Crashed(crash) if crash.message == "boom"));
+}
+
+#[tokio::test]
+async fn stop_only_stops_the_actor() {
+    let link = Spawner::<Fragile>::new(0)
+        .crash_policy(CrashPolicy::Stop)
+        .spawn();
+
+    assert!(link.ask_dyn(Boom).await.is_err());
+    link.wait().await;
+    assert!(!link.alive());
+}
+
+#[tokio::test]
+async fn restart_reinitializes_behind_the_same_link() {
+    let link = Spawner::<Fragile>::new(10)
+        .restartable()
+        .crash_policy(CrashPolicy::Restart)
+        .spawn();
+
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 11);
+    assert!(link.ask_dyn(Boom).await.is_err());
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 11);
+    assert!(link.alive());
+}
+
+#[tokio::test]
+async fn escalate_passes_the_panic_message_to_the_parent() {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let link = Spawner::<Fragile>::new(0)
+        .crash_policy(CrashPolicy::Escalate)
+        .on_escalate(move |crash| {
+            let _ = tx.send(crash);
+        })
+        .spawn();
+
+    assert!(link.ask_dyn(Boom).await.is_err());
+
+    let crash = rx.recv().await.unwrap();
+    assert_eq!(crash.message, "boom");
+    assert!(crash.actor.ends_with("Fragile"));
+}
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorExit;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::RestartPolicy;
+use actor12::Spawner;
+use actor12::prelude::InitFuture;
+
+#[derive(Clone, Default)]
+struct Spec {
+    attempts: Arc<AtomicU32>,
+    /// Attempts that fail before `init` succeeds.
+    failures: u32,
+    delay: Duration,
+}
+
+struct Slow;
+
+impl Actor for Slow {
+    type Cancel = &'static str;
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = Spec;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        let spec = ctx.spec;
+        async move {
+            tokio::time::sleep(spec.delay).await;
+            let attempt = spec.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            if attempt <= spec.failures {
+                return Err("not yet");
+            }
+            Ok(Slow)
+        }
+    }
+}
+
+struct Ping;
+
+impl Handler<Ping> for Slow {
+    type Reply = anyhow::Result<&'static str>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Ping) -> Self::Reply {
+        Ok("pong")
+    }
+}
+
+fn retry(max_restarts: usize) -> RestartPolicy {
+    RestartPolicy {
+        max_restarts,
+        jitter: 0.0,
+        ..RestartPolicy::default()
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn messages_sent_before_ready_are_buffered() {
+    let link = Spawner::<Slow>::new(Spec {
+        delay: Duration::from_secs(1),
+        ..Spec::default()
+    })
+    .spawn();
+
+    let reply = link.ask_dyn_async(Ping).await;
+    assert!(link.ready().await.is_ok());
+    assert_eq!(reply.await.unwrap(), "pong");
+}
+
+#[tokio::test]
+async fn ready_reports_the_init_error() {
+    let link = Spawner::<Slow>::new(Spec {
+        failures: 1,
+        ..Spec::default()
+    })
+    .spawn();
+
+    assert!(matches!(link.ready().await, Err(ActorExit::InitFailed("not yet"))));
+}
+
+#[tokio::test(start_paused = true)]
+async fn init_times_out() {
+    let link = Spawner::<Slow>::new(Spec {
+        delay: Duration::from_secs(10),
+        ..Spec::default()
+    })
+    .init_timeout(Duration::from_secs(1))
+    .spawn();
+
+    assert!(matches!(link.ready().await, Err(ActorExit::InitTimedOut)));
+    assert!(matches!(link.join().await, ActorExit::InitTimedOut));
+}
+
+#[tokio::test(start_paused = true)]
+async fn failed_init_is_retried() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let link = Spawner::<Slow>::new(Spec {
+        attempts: attempts.clone(),
+        failures: 2,
+        ..Spec::default()
+    })
+    .init_retry(retry(3))
+    .spawn();
+
+    assert!(link.ready().await.is_ok());
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn init_retry_gives_up() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let link = Spawner::<Slow>::new(Spec {
+        attempts: attempts.clone(),
+        failures: u32::MAX,
+        ..Spec::default()
+    })
+    .init_retry(retry(2))
+    .spawn();
+
+    assert!(matches!(link.ready().await, Err(ActorExit::InitFailed("not yet"))));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
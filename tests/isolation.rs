@@ -0,0 +1,114 @@
+use actor12::Actor;
+use actor12::ActorError;
+use actor12::Call;
+use actor12::Crash;
+use actor12::CrashPolicy;
+use actor12::Handler;
+use actor12::HandlerPanic;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Spawner;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+struct Counter {
+    count: u32,
+    policy: HandlerPanic,
+}
+
+impl Actor for Counter {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = HandlerPanic;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Counter {
+            count: 0,
+            policy: ctx.spec,
+        }))
+    }
+
+    fn on_handler_panic(&mut self, _crash: &Crash) -> HandlerPanic {
+        self.policy
+    }
+}
+
+struct Boom;
+struct Increment;
+
+impl Handler<Boom> for Counter {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Boom) -> Self::Reply {
+        panic!("boom");
+    }
+}
+
+impl Handler<Increment> for Counter {
+    type Reply = anyhow::Result<u32>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Increment) -> Self::Reply {
+        self.count += 1;
+        Ok(self.count)
+    }
+}
+
+fn panicked(reply: anyhow::Result<()>) -> bool {
+    matches!(
+        reply.unwrap_err().downcast_ref::<ActorError>(),
+        Some(ActorError::HandlerPanicked(message)) if message == "boom"
+    )
+}
+
+#[tokio::test]
+async fn resume_keeps_the_state() {
+    let link = Spawner::<Counter>::new(HandlerPanic::Resume)
+        .crash_policy(CrashPolicy::Stop)
+        .catch_handler_panics()
+        .spawn();
+
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 1);
+    assert!(panicked(link.ask_dyn(Boom).await));
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn restart_reinitialises_the_actor() {
+    let link = Spawner::<Counter>::new(HandlerPanic::Restart)
+        .crash_policy(CrashPolicy::Stop)
+        .catch_handler_panics()
+        .restartable()
+        .spawn();
+
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 1);
+    assert!(panicked(link.ask_dyn(Boom).await));
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn propagate_crashes_the_actor() {
+    let link = Spawner::<Counter>::new(HandlerPanic::Propagate)
+        .crash_policy(CrashPolicy::Stop)
+        .catch_handler_panics()
+        .spawn();
+
+    assert!(panicked(link.ask_dyn(Boom).await));
+    link.wait().await;
+    assert!(!link.alive());
+}
+
+#[tokio::test]
+async fn panics_are_not_caught_unless_asked_for() {
+    let link = Spawner::<Counter>::new(HandlerPanic::Resume)
+        .crash_policy(CrashPolicy::Stop)
+        .spawn();
+
+    assert!(link.ask_dyn(Boom).await.is_err());
+    link.wait().await;
+    assert!(!link.alive());
+}
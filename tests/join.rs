@@ -0,0 +1,75 @@
+use actor12::Actor;
+use actor12::ActorExit;
+use actor12::Call;
+use actor12::CrashPolicy;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Spawner;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+struct Job;
+
+impl Actor for Job {
+    type Cancel = &'static str;
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = bool;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(if ctx.spec { Ok(Job) } else { Err("no init") })
+    }
+}
+
+struct Boom;
+
+impl Handler<Boom> for Job {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Boom) -> Self::Reply {
+        panic!("boom");
+    }
+}
+
+#[tokio::test]
+async fn join_reports_a_clean_stop() {
+    let link = actor12::spawn::<Job>(true);
+    link.cancel("stop");
+
+    let exit = link.join().await;
+    assert!(exit.is_clean());
+    assert!(matches!(exit, ActorExit::Completed(reason) if *reason.value() == "stop"));
+    assert!(!link.alive());
+}
+
+#[tokio::test]
+async fn join_reports_a_crash() {
+    let link = Spawner::<Job>::new(true)
+        .crash_policy(CrashPolicy::Stop)
+        .spawn();
+    let _ = link.ask_dyn(Boom).await;
+
+    let exit = link.join().await;
+    assert!(matches!(exit, ActorExit::Crashed(crash) if crash.message == "boom"));
+}
+
+#[tokio::test]
+async fn join_reports_an_init_failure() {
+    let link = actor12::spawn::<Job>(false);
+
+    assert!(matches!(link.join().await, ActorExit::InitFailed("no init")));
+}
+
+#[tokio::test]
+async fn weak_join_outlives_the_last_link() {
+    let link = actor12::spawn::<Job>(true);
+    let weak = link.downgrade();
+    drop(link);
+
+    assert!(weak.join().await.is_clean());
+}
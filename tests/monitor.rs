@@ -0,0 +1,157 @@
+use actor12::Actor;
+use actor12::ActorExit;
+use actor12::Call;
+use actor12::CrashPolicy;
+use actor12::Down;
+use actor12::Handler;
+use actor12::Init;
+use actor12::Link;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Spawner;
+use actor12::WeakLink;
+use actor12::cancel::CancelToken;
+use actor12::prelude::InitFuture;
+use futures::future;
+use tokio::sync::mpsc;
+
+struct Target;
+
+impl Actor for Target {
+    type Cancel = &'static str;
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = bool;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(if ctx.spec { Ok(Target) } else { Err("no init") })
+    }
+}
+
+struct Boom;
+
+impl Handler<Boom> for Target {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Boom) -> Self::Reply {
+        panic!("boom");
+    }
+}
+
+type Report = (WeakLink<Target>, ActorExit<&'static str>);
+
+struct Watcher {
+    downs: mpsc::UnboundedSender<Report>,
+}
+
+impl Actor for Watcher {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = mpsc::UnboundedSender<Report>;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Watcher { downs: ctx.spec }))
+    }
+}
+
+impl Handler<Down<Target>> for Watcher {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, down: Down<Target>) -> Self::Reply {
+        let _ = self.downs.send((down.link, down.exit));
+        Ok(())
+    }
+}
+
+fn watcher() -> (Link<Watcher>, mpsc::UnboundedReceiver<Report>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    (actor12::spawn::<Watcher>(tx), rx)
+}
+
+#[tokio::test]
+async fn down_carries_the_cancel_reason() {
+    let (watcher, mut downs) = watcher();
+    let target = actor12::spawn::<Target>(true);
+    target.monitor(&watcher.downgrade());
+
+    target.cancel("done");
+
+    let (link, exit) = downs.recv().await.unwrap();
+    assert!(link == target.downgrade());
+    assert!(matches!(exit, ActorExit::Completed(reason) if *reason.value() == "done"));
+}
+
+#[tokio::test]
+async fn down_reports_a_crash() {
+    let (watcher, mut downs) = watcher();
+    let target = Spawner::<Target>::new(true)
+        .crash_policy(CrashPolicy::Stop)
+        .spawn();
+    target.monitor(&watcher.downgrade());
+
+    let _ = target.ask_dyn(Boom).await;
+
+    let (_, exit) = downs.recv().await.unwrap();
+    assert!(matches!(exit, ActorExit::Crashed(crash) if crash.message == "boom"));
+}
+
+#[tokio::test]
+async fn down_reports_an_init_failure() {
+    let (watcher, mut downs) = watcher();
+    let target = actor12::spawn::<Target>(false);
+    target.monitor(&watcher.downgrade());
+
+    let (_, exit) = downs.recv().await.unwrap();
+    assert!(matches!(exit, ActorExit::InitFailed("no init")));
+}
+
+#[tokio::test]
+async fn monitor_does_not_keep_the_target_alive() {
+    let (watcher, mut downs) = watcher();
+    let target = actor12::spawn::<Target>(true);
+    let weak = target.downgrade();
+    weak.monitor(&watcher.downgrade());
+
+    drop(target);
+
+    let (link, exit) = downs.recv().await.unwrap();
+    assert!(link == weak);
+    assert!(link.upgrade().is_none());
+    assert!(matches!(exit, ActorExit::Completed(_)));
+}
+
+#[tokio::test]
+async fn demonitor_stops_delivery() {
+    let (watcher, mut downs) = watcher();
+    let target = actor12::spawn::<Target>(true);
+    target.monitor(&watcher.downgrade()).demonitor();
+
+    target.cancel_and_wait("done").await;
+    drop(watcher);
+
+    assert!(downs.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn links_without_a_task_stop_when_their_mailbox_closes() {
+    let (watcher, mut downs) = watcher();
+    let (tx, rx) = mpsc::channel(1);
+    let target = Link::<Target>::new(tx, CancelToken::new(), ());
+    target.monitor(&watcher.downgrade());
+
+    assert!(target.ready().await.is_ok());
+    let join = target.join();
+    target.cancel("done");
+    drop(rx);
+
+    assert!(matches!(join.await, ActorExit::Completed(reason) if *reason.value() == "done"));
+    let (_, exit) = downs.recv().await.unwrap();
+    assert!(exit.is_clean());
+}
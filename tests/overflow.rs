@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorError;
+use actor12::Block;
+use actor12::Call;
+use actor12::DropNewest;
+use actor12::DropOldest;
+use actor12::Handler;
+use actor12::Init;
+use actor12::Multi;
+use actor12::Overflow;
+use actor12::OverflowChannel;
+use actor12::OverflowPolicy;
+use actor12::Reject;
+use actor12::prelude::InitFuture;
+use futures::FutureExt;
+
+/// Takes a while to start, so its two-message mailbox overflows.
+struct Sink<P> {
+    log: Vec<(char, u32)>,
+    _policy: std::marker::PhantomData<P>,
+}
+
+impl<P: OverflowPolicy<Multi<Self>>> Actor for Sink<P> {
+    type Cancel = ();
+    type State = ();
+    type Channel = OverflowChannel<Self::Message, P>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn mailbox_capacity() -> usize {
+        2
+    }
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(Sink {
+                log: Vec::new(),
+                _policy: std::marker::PhantomData,
+            })
+        }
+    }
+}
+
+struct Sample(char, u32);
+struct Log;
+
+impl<P: OverflowPolicy<Multi<Self>>> Handler<Sample> for Sink<P> {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, msg: Sample) -> Self::Reply {
+        self.log.push((msg.0, msg.1));
+        Ok(())
+    }
+}
+
+impl<P: OverflowPolicy<Multi<Self>>> Handler<Log> for Sink<P> {
+    type Reply = anyhow::Result<Vec<(char, u32)>>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Log) -> Self::Reply {
+        Ok(self.log.clone())
+    }
+}
+
+/// Latest sample per key wins.
+struct Latest;
+
+impl OverflowPolicy<Multi<Sink<Latest>>> for Latest {
+    type Key = char;
+
+    fn overflow() -> Overflow {
+        Overflow::Coalesce
+    }
+
+    fn key(msg: &Multi<Sink<Latest>>) -> Option<char> {
+        msg.downcast_ref::<Sample>().map(|sample| sample.0)
+    }
+}
+
+fn is_overflow(err: &anyhow::Error) -> bool {
+    matches!(err.downcast_ref::<ActorError>(), Some(ActorError::Overflow))
+}
+
+async fn log<P: OverflowPolicy<Multi<Sink<P>>>>(link: &actor12::Link<Sink<P>>) -> Vec<(char, u32)> {
+    // Let the actor start and drain its mailbox.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    link.ask_dyn(Log).await.unwrap()
+}
+
+#[tokio::test(start_paused = true)]
+async fn drop_oldest() {
+    let link = actor12::spawn::<Sink<DropOldest>>(());
+
+    let first = link.ask_dyn_async(Sample('a', 1)).await;
+    link.tell_dyn(Sample('a', 2)).await;
+    link.tell_dyn(Sample('a', 3)).await;
+
+    assert!(is_overflow(&first.await.unwrap_err()));
+    assert_eq!(link.dropped(), 1);
+    assert_eq!(log(&link).await, [('a', 2), ('a', 3)]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn drop_newest() {
+    let link = actor12::spawn::<Sink<DropNewest>>(());
+
+    link.tell_dyn(Sample('a', 1)).await;
+    link.tell_dyn(Sample('a', 2)).await;
+    let third = link.ask_dyn_async(Sample('a', 3)).await;
+
+    assert!(is_overflow(&third.await.unwrap_err()));
+    assert_eq!(link.dropped(), 1);
+    assert_eq!(log(&link).await, [('a', 1), ('a', 2)]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn reject() {
+    let link = actor12::spawn::<Sink<Reject>>(());
+
+    link.tell_dyn(Sample('a', 1)).await;
+    link.tell_dyn(Sample('a', 2)).await;
+    let err = link.ask_dyn(Sample('a', 3)).await.unwrap_err();
+
+    assert_eq!(err.to_string(), "Mailbox is full");
+    assert_eq!(link.dropped(), 0);
+    assert_eq!(log(&link).await, [('a', 1), ('a', 2)]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn coalesce() {
+    let link = actor12::spawn::<Sink<Latest>>(());
+
+    // Replaced in place while there is room.
+    let stale = link.ask_dyn_async(Sample('a', 1)).await;
+    link.tell_dyn(Sample('a', 2)).await;
+    assert!(is_overflow(&stale.await.unwrap_err()));
+    assert_eq!(link.len(), 1);
+
+    // Full: a new key makes room by dropping the oldest message, a known
+    // key replaces its message.
+    link.tell_dyn(Sample('b', 1)).await;
+    link.tell_dyn(Sample('c', 1)).await;
+    link.tell_dyn(Sample('b', 2)).await;
+
+    assert_eq!(link.dropped(), 3);
+    assert_eq!(log(&link).await, [('b', 2), ('c', 1)]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn block() {
+    let link = actor12::spawn::<Sink<Block>>(());
+
+    link.tell_dyn(Sample('a', 1)).await;
+    link.tell_dyn(Sample('a', 2)).await;
+    assert!(link.tell_dyn(Sample('a', 3)).now_or_never().is_none());
+    assert!(link.try_tell_dyn(Sample('a', 3)).is_err());
+
+    link.tell_dyn(Sample('a', 3)).await;
+    assert_eq!(log(&link).await, [('a', 1), ('a', 2), ('a', 3)]);
+}
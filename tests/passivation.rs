@@ -0,0 +1,158 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorContext;
+use actor12::ActorError;
+use actor12::ActorExit;
+use actor12::ActorSystem;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::Link;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Spawner;
+use actor12::cancel::CancelReason;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+/// Stands in for the entity's persistent storage.
+#[derive(Clone, Default)]
+struct Store {
+    value: Arc<AtomicU32>,
+    inits: Arc<AtomicU32>,
+    passivations: Arc<AtomicU32>,
+}
+
+struct Entity {
+    value: u32,
+    store: Store,
+}
+
+impl Actor for Entity {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = Store;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        let store = ctx.spec;
+        store.inits.fetch_add(1, Ordering::SeqCst);
+        future::ready(Ok(Entity {
+            value: store.value.load(Ordering::SeqCst),
+            store,
+        }))
+    }
+
+    async fn terminate(self, ctx: ActorContext<Self>, _reason: CancelReason<Self::Cancel>) {
+        if ctx.is_passivating() {
+            self.store.passivations.fetch_add(1, Ordering::SeqCst);
+        }
+        self.store.value.store(self.value, Ordering::SeqCst);
+    }
+}
+
+struct Increment;
+
+impl Handler<Increment> for Entity {
+    type Reply = anyhow::Result<u32>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Increment) -> Self::Reply {
+        self.value += 1;
+        Ok(self.value)
+    }
+}
+
+fn spawn(store: &Store) -> Link<Entity> {
+    Spawner::<Entity>::new(store.clone())
+        .passivate_after(Duration::from_secs(10))
+        .spawn()
+}
+
+#[tokio::test(start_paused = true)]
+async fn idle_actor_is_passivated_and_reactivated() {
+    let store = Store::default();
+    let link = spawn(&store);
+
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 1);
+    tokio::time::sleep(Duration::from_secs(11)).await;
+
+    assert_eq!(store.passivations.load(Ordering::SeqCst), 1);
+    assert!(link.alive());
+
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 2);
+    assert_eq!(store.inits.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn busy_actor_is_not_passivated() {
+    let store = Store::default();
+    let link = spawn(&store);
+
+    for _ in 0..6 {
+        link.ask_dyn(Increment).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+
+    assert_eq!(store.passivations.load(Ordering::SeqCst), 0);
+    assert_eq!(store.inits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancelling_a_passivated_actor_completes_it() {
+    let store = Store::default();
+    let link = spawn(&store);
+
+    tokio::time::sleep(Duration::from_secs(11)).await;
+    link.cancel(());
+
+    assert!(matches!(link.join().await, ActorExit::Completed(_)));
+    assert_eq!(store.passivations.load(Ordering::SeqCst), 1);
+    assert_eq!(store.inits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn expired_messages_do_not_wake_a_passivated_actor() {
+    let store = Store::default();
+    let link = spawn(&store);
+
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 1);
+    tokio::time::sleep(Duration::from_secs(11)).await;
+
+    let err = link.ask_dyn_with_ttl(Increment, Duration::ZERO).await.unwrap_err();
+    assert!(matches!(err.downcast_ref::<ActorError>(), Some(ActorError::Expired)));
+    assert_eq!(store.inits.load(Ordering::SeqCst), 1);
+
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 2);
+    assert_eq!(store.inits.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn passivated_actor_releases_its_task() {
+    let store = Store::default();
+    let system = ActorSystem::new();
+    let link = Spawner::<Entity>::new(store.clone())
+        .passivate_after(Duration::from_secs(10))
+        .system(&system)
+        .spawn();
+
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 1);
+    assert_eq!(system.stats().live::<Entity>(), 1);
+
+    tokio::time::sleep(Duration::from_secs(11)).await;
+    assert_eq!(system.stats().live::<Entity>(), 0);
+
+    assert_eq!(link.ask_dyn(Increment).await.unwrap(), 2);
+    assert_eq!(system.stats().live::<Entity>(), 1);
+    assert_eq!(system.stats().total::<Entity>(), 2);
+
+    link.cancel(());
+    assert!(matches!(link.join().await, ActorExit::Completed(_)));
+    assert_eq!(system.stats().live::<Entity>(), 0);
+}
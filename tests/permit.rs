@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorError;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::prelude::InitFuture;
+use futures::FutureExt;
+
+/// Takes a while to start, so its mailbox fills up.
+struct Adder {
+    total: u32,
+}
+
+impl Actor for Adder {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn mailbox_capacity() -> usize {
+        2
+    }
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(Adder { total: 0 })
+        }
+    }
+}
+
+struct Add(u32);
+
+impl Handler<Add> for Adder {
+    type Reply = anyhow::Result<u32>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, msg: Add) -> Self::Reply {
+        self.total += msg.0;
+        Ok(self.total)
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn permits_hold_mailbox_slots() {
+    let link = actor12::spawn::<Adder>(());
+    assert_eq!(link.max_capacity(), 2);
+    assert_eq!(link.capacity(), 2);
+    assert!(link.is_empty());
+
+    let first = link.reserve().await.unwrap();
+    assert_eq!(link.capacity(), 1);
+    assert_eq!(link.len(), 1);
+
+    first.tell_dyn(Add(1));
+    let second = link.reserve().await.unwrap();
+    assert_eq!(link.len(), 2);
+    assert!(link.reserve().now_or_never().is_none());
+
+    let reply = second.ask_dyn(Add(2));
+    assert_eq!(reply.await.unwrap(), 3);
+    assert!(link.is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn dropped_permit_frees_its_slot() {
+    let link = actor12::spawn::<Adder>(());
+
+    let permit = link.reserve().await.unwrap();
+    assert_eq!(link.capacity(), 1);
+    drop(permit);
+    assert_eq!(link.capacity(), 2);
+}
+
+#[tokio::test]
+async fn reserve_fails_once_the_actor_is_gone() {
+    let link = actor12::spawn::<Adder>(());
+    link.cancel_and_wait(()).await;
+
+    let err = link.reserve().await.err().unwrap();
+    assert!(matches!(err, ActorError::Dead));
+}
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::Multi;
+use actor12::SharedChannel;
+use actor12::prelude::InitFuture;
+use futures::future;
+use tokio::time::Instant;
+
+/// Numbers itself from the shared counter in its spec.
+struct Worker {
+    id: usize,
+}
+
+impl Actor for Worker {
+    type Cancel = ();
+    type State = ();
+    type Channel = SharedChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = Arc<AtomicUsize>;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        let id = ctx.spec.fetch_add(1, Ordering::SeqCst);
+        future::ready(Ok(Worker { id }))
+    }
+}
+
+/// Takes this long to handle, replies with the worker's id.
+struct Work(Duration);
+struct Quit;
+
+impl Handler<Work> for Worker {
+    type Reply = anyhow::Result<usize>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, msg: Work) -> Self::Reply {
+        tokio::time::sleep(msg.0).await;
+        Ok(self.id)
+    }
+}
+
+impl Handler<Quit> for Worker {
+    type Reply = anyhow::Result<usize>;
+
+    async fn handle(&mut self, ctx: Call<'_, Self, Self::Reply>, _: Quit) -> Self::Reply {
+        ctx.token.cancel(());
+        Ok(self.id)
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn idle_workers_take_the_next_message() {
+    let started = Arc::new(AtomicUsize::new(0));
+    let link = actor12::spawn_pool::<Worker>(3, started.clone());
+    link.ready().await.unwrap();
+    assert_eq!(started.load(Ordering::SeqCst), 3);
+
+    let start = Instant::now();
+    let slow = tokio::spawn({
+        let link = link.clone();
+        async move { link.ask_dyn(Work(Duration::from_secs(10))).await }
+    });
+    tokio::task::yield_now().await;
+
+    // The other two workers share the quick messages meanwhile.
+    for _ in 0..6 {
+        link.ask_dyn(Work(Duration::from_millis(10))).await.unwrap();
+    }
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    slow.await.unwrap().unwrap();
+    assert!(start.elapsed() >= Duration::from_secs(10));
+}
+
+#[tokio::test(start_paused = true)]
+async fn messages_spread_over_the_pool() {
+    let link = actor12::spawn_pool::<Worker>(4, Default::default());
+
+    let replies = future::join_all((0..4).map(|_| link.ask_dyn(Work(Duration::from_millis(100))))).await;
+    let mut ids: Vec<usize> = replies.into_iter().map(Result::unwrap).collect();
+    ids.sort();
+    assert_eq!(ids, [0, 1, 2, 3]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_worker_stopping_leaves_the_rest_running() {
+    let link = actor12::spawn_pool::<Worker>(2, Default::default());
+
+    let quit = link.ask_dyn(Quit).await.unwrap();
+    let id = link.ask_dyn(Work(Duration::ZERO)).await.unwrap();
+    assert_ne!(id, quit);
+    assert!(link.alive());
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancelling_the_link_stops_every_worker() {
+    let link = actor12::spawn_pool::<Worker>(3, Default::default());
+    link.ready().await.unwrap();
+
+    link.cancel(());
+    assert!(link.join().await.is_clean());
+    assert!(!link.alive());
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::Call;
+use actor12::Down;
+use actor12::Handler;
+use actor12::Init;
+use actor12::Multi;
+use actor12::Priority;
+use actor12::PriorityChannel;
+use actor12::prelude::InitFuture;
+use futures::FutureExt;
+use futures::future;
+
+/// Takes a while to start, so messages queue up before it receives any.
+struct Recorder {
+    log: Vec<&'static str>,
+}
+
+impl Actor for Recorder {
+    type Cancel = ();
+    type State = ();
+    type Channel = PriorityChannel<Self::Message, 3>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn mailbox_capacity() -> usize {
+        6
+    }
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(Recorder { log: Vec::new() })
+        }
+    }
+}
+
+/// Starts right away, so it can stop while the recorder is still starting.
+struct Target;
+
+impl Actor for Target {
+    type Cancel = ();
+    type State = ();
+    type Channel = PriorityChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Target))
+    }
+}
+
+struct Record(&'static str);
+struct Log;
+
+impl Handler<Record> for Recorder {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, msg: Record) -> Self::Reply {
+        self.log.push(msg.0);
+        Ok(())
+    }
+}
+
+impl Handler<Down<Target>> for Recorder {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Down<Target>) -> Self::Reply {
+        self.log.push("down");
+        Ok(())
+    }
+}
+
+impl Handler<Log> for Recorder {
+    type Reply = anyhow::Result<Vec<&'static str>>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Log) -> Self::Reply {
+        Ok(std::mem::take(&mut self.log))
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn higher_lanes_are_received_first() {
+    let link = actor12::spawn::<Recorder>(());
+
+    link.tell_dyn(Record("normal 1")).await;
+    link.tell_dyn_with_priority(Record("high 1"), Priority::new(2)).await;
+    link.tell_dyn_with_priority(Record("medium"), Priority::new(1)).await;
+    link.tell_dyn(Record("normal 2")).await;
+    link.tell_dyn_with_priority(Record("high 2"), Priority::new(7)).await;
+
+    let log = link.ask_dyn_with_priority(Log, Priority::NORMAL).await.unwrap();
+    assert_eq!(
+        log,
+        ["high 1", "high 2", "medium", "normal 1", "normal 2"]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn down_bypasses_a_full_mailbox() {
+    let link = actor12::spawn::<Recorder>(());
+    let target = actor12::spawn::<Target>(());
+    target.monitor(&link.downgrade());
+
+    for _ in 0..6 {
+        link.tell_dyn(Record("data")).await;
+    }
+    assert_eq!(link.capacity(), 0);
+    assert!(link.tell_dyn(Record("blocked")).now_or_never().is_none());
+
+    target.cancel(());
+    target.join().await;
+    let log = link.ask_dyn(Log).await.unwrap();
+    assert_eq!(log, ["down", "data", "data", "data", "data", "data", "data"]);
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::ReceiveTimeout;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+struct Flusher {
+    flushes: u32,
+}
+
+impl Actor for Flusher {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(mut ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        ctx.set_receive_timeout(Duration::from_secs(1));
+        future::ready(Ok(Flusher { flushes: 0 }))
+    }
+}
+
+impl Handler<ReceiveTimeout> for Flusher {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: ReceiveTimeout) -> Self::Reply {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
+struct Flushes;
+struct Stop;
+struct Slow;
+
+impl Handler<Flushes> for Flusher {
+    type Reply = anyhow::Result<u32>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Flushes) -> Self::Reply {
+        Ok(self.flushes)
+    }
+}
+
+impl Handler<Stop> for Flusher {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, mut ctx: Call<'_, Self, Self::Reply>, _: Stop) -> Self::Reply {
+        ctx.cancel_receive_timeout();
+        Ok(())
+    }
+}
+
+impl Handler<Slow> for Flusher {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, mut ctx: Call<'_, Self, Self::Reply>, _: Slow) -> Self::Reply {
+        ctx.set_receive_timeout(Duration::from_secs(5));
+        Ok(())
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn fires_only_while_idle() {
+    let link = actor12::spawn::<Flusher>(());
+
+    for _ in 0..6 {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(link.ask_dyn(Flushes).await.unwrap(), 0);
+    }
+
+    tokio::time::sleep(Duration::from_millis(3500)).await;
+    assert_eq!(link.ask_dyn(Flushes).await.unwrap(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn can_be_cancelled() {
+    let link = actor12::spawn::<Flusher>(());
+
+    link.ask_dyn(Stop).await.unwrap();
+    tokio::time::sleep(Duration::from_secs(10)).await;
+
+    assert_eq!(link.ask_dyn(Flushes).await.unwrap(), 0);
+}
+
+#[tokio::test(start_paused = true)]
+async fn can_be_reset() {
+    let link = actor12::spawn::<Flusher>(());
+
+    link.ask_dyn(Slow).await.unwrap();
+    tokio::time::sleep(Duration::from_secs(4)).await;
+    assert_eq!(link.ask_dyn(Flushes).await.unwrap(), 0);
+
+    tokio::time::sleep(Duration::from_secs(6)).await;
+    assert_eq!(link.ask_dyn(Flushes).await.unwrap(), 1);
+}
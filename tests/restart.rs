@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::RestartPolicy;
+use actor12::Strategy;
+use actor12::Supervisor;
+use actor12::SupervisorExit;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+struct Failing;
+
+impl Actor for Failing {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Err(()))
+    }
+}
+
+fn policy() -> RestartPolicy {
+    RestartPolicy {
+        max_restarts: 2,
+        within: Duration::from_secs(10),
+        min_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(150),
+        jitter: 0.0,
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn backoff_grows_until_the_limit() {
+    let mut restarts = policy().restarts();
+
+    assert_eq!(restarts.record(), Some(Duration::from_millis(100)));
+    assert_eq!(restarts.record(), Some(Duration::from_millis(150)));
+    assert_eq!(restarts.record(), None);
+
+    tokio::time::advance(Duration::from_secs(10)).await;
+    assert_eq!(restarts.record(), Some(Duration::from_millis(100)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn jitter_stays_within_bounds() {
+    let mut restarts = RestartPolicy {
+        jitter: 0.5,
+        ..policy()
+    }
+    .restarts();
+
+    let delay = restarts.record().unwrap();
+    assert!(delay >= Duration::from_millis(100));
+    assert!(delay <= Duration::from_millis(150));
+}
+
+#[tokio::test(start_paused = true)]
+async fn supervisor_escalates_when_children_keep_failing() {
+    let start = tokio::time::Instant::now();
+    let supervisor = Supervisor::<Failing>::with_policy(Strategy::OneForOne, policy(), vec![()]);
+
+    assert_eq!(supervisor.wait().await, SupervisorExit::Escalated);
+    assert_eq!(start.elapsed(), Duration::from_millis(250));
+}
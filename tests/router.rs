@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorError;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::Link;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Router;
+use actor12::Routing;
+use actor12::prelude::InitFuture;
+
+/// Takes a while to start, so messages queue up before it receives any.
+struct Member {
+    id: usize,
+}
+
+impl Actor for Member {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = usize;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        let id = ctx.spec;
+        async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(Member { id })
+        }
+    }
+}
+
+/// Replies with the member's id.
+#[derive(Clone)]
+struct Which;
+
+/// Replies with the member's id; routed by `user`.
+struct Get {
+    user: u32,
+}
+
+impl Handler<Which> for Member {
+    type Reply = anyhow::Result<usize>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Which) -> Self::Reply {
+        Ok(self.id)
+    }
+}
+
+impl Handler<Get> for Member {
+    type Reply = anyhow::Result<usize>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Get) -> Self::Reply {
+        Ok(self.id)
+    }
+}
+
+fn members(n: usize) -> Vec<Link<Member>> {
+    (0..n).map(actor12::spawn::<Member>).collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn round_robin_takes_turns() {
+    let router = Router::new(Routing::RoundRobin).with_members(members(3));
+
+    let mut ids = Vec::new();
+    for _ in 0..6 {
+        ids.push(router.ask_dyn(Which).await.unwrap());
+    }
+    assert_eq!(ids, [0, 1, 2, 0, 1, 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn random_reaches_every_member() {
+    let router = Router::new(Routing::Random).with_members(members(4));
+
+    let mut seen = [false; 4];
+    for _ in 0..100 {
+        seen[router.ask_dyn(Which).await.unwrap()] = true;
+    }
+    assert_eq!(seen, [true; 4]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn least_loaded_picks_the_shortest_mailbox() {
+    let links = members(3);
+    let router = Router::new(Routing::LeastLoaded).with_members(links.clone());
+
+    links[0].tell_dyn(Which).await;
+    links[0].tell_dyn(Which).await;
+    links[1].tell_dyn(Which).await;
+
+    router.tell_dyn(Which).await;
+    assert_eq!(links[2].len(), 1);
+    router.tell_dyn(Which).await;
+    router.tell_dyn(Which).await;
+    assert_eq!(links.iter().map(Link::len).collect::<Vec<_>>(), [2, 2, 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn consistent_hash_keeps_keys_on_their_member() {
+    let links = members(4);
+    let router = Router::new(Routing::ConsistentHash)
+        .hash_key(|get: &Get| get.user)
+        .with_members(links.clone());
+
+    let mut owners = Vec::new();
+    for user in 0..32 {
+        let owner = router.ask_dyn(Get { user }).await.unwrap();
+        assert_eq!(router.ask_dyn(Get { user }).await.unwrap(), owner);
+        owners.push(owner);
+    }
+
+    // Only the keys of the removed member move.
+    assert!(router.remove(&links[3]));
+    for (user, owner) in (0..32).zip(owners) {
+        let now = router.ask_dyn(Get { user }).await.unwrap();
+        if owner != 3 {
+            assert_eq!(now, owner);
+        }
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn gather_asks_every_member() {
+    let router = Router::new(Routing::RoundRobin).with_members(members(3));
+
+    let ids: Vec<usize> = router.gather_dyn(Which).await.into_iter().map(Result::unwrap).collect();
+    assert_eq!(ids, [0, 1, 2]);
+
+    router.broadcast_dyn(Which).await;
+    assert!(router.members().iter().all(|link| link.len() == 1));
+}
+
+#[tokio::test(start_paused = true)]
+async fn members_can_change_at_runtime() {
+    let router = Router::<Member>::default();
+    let err = router.ask_dyn(Which).await.unwrap_err();
+    assert!(matches!(err.downcast_ref::<ActorError>(), Some(ActorError::NoMembers)));
+
+    let link = actor12::spawn::<Member>(7);
+    router.add(link.clone());
+    router.add(link.clone());
+    assert_eq!(router.len(), 1);
+    assert_eq!(router.ask_dyn(Which).await.unwrap(), 7);
+
+    assert!(router.remove(&link));
+    assert!(!router.remove(&link));
+    assert!(router.is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn stopped_members_are_dropped() {
+    let links = members(3);
+    let router = Router::new(Routing::RoundRobin).with_members(links.clone());
+
+    links[1].cancel(());
+    links[1].join().await;
+
+    for _ in 0..4 {
+        assert_ne!(router.ask_dyn(Which).await.unwrap(), 1);
+    }
+    assert_eq!(router.len(), 2);
+}
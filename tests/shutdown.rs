@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorContext;
+use actor12::ActorExit;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::Link;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Shutdown;
+use actor12::cancel::CancelReason;
+use actor12::cancel::CancelToken;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+struct Stubborn;
+
+impl Actor for Stubborn {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Stubborn))
+    }
+
+    async fn terminate(self, _ctx: ActorContext<Self>, _reason: CancelReason<Self::Cancel>) {
+        // Never finishes on its own.
+        future::pending::<()>().await
+    }
+}
+
+impl Handler<()> for Stubborn {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: ()) -> Self::Reply {
+        Ok(())
+    }
+}
+
+struct Polite;
+
+impl Actor for Polite {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Polite))
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn shutdown_is_graceful_when_the_actor_stops_in_time() {
+    let link = actor12::spawn::<Polite>(());
+
+    let outcome = link.shutdown((), Duration::from_secs(1)).await;
+
+    assert_eq!(outcome, Shutdown::Graceful);
+    assert!(link.join().await.is_clean());
+}
+
+#[tokio::test(start_paused = true)]
+async fn shutdown_aborts_after_the_grace_period() {
+    let link = actor12::spawn::<Stubborn>(());
+    let start = tokio::time::Instant::now();
+
+    let outcome = link.shutdown((), Duration::from_secs(1)).await;
+
+    assert_eq!(outcome, Shutdown::Forced);
+    assert_eq!(start.elapsed(), Duration::from_secs(1));
+    assert!(matches!(link.join().await, ActorExit::Aborted));
+    // The aborted task drops the mailbox once the runtime gets to it.
+    while link.alive() {
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn dyn_link_shutdown_aborts_after_the_grace_period() {
+    let link = actor12::spawn::<Stubborn>(());
+
+    let outcome = link.to_dyn::<()>().shutdown(Duration::from_secs(1)).await;
+
+    assert_eq!(outcome, Shutdown::Forced);
+}
+
+#[tokio::test(start_paused = true)]
+async fn shutdown_of_a_link_without_a_task_is_graceful_once_closed() {
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    let link = Link::<Stubborn>::new(tx, CancelToken::new(), ());
+    drop(rx);
+    let start = tokio::time::Instant::now();
+
+    let outcome = link.shutdown((), Duration::from_secs(1)).await;
+
+    assert_eq!(outcome, Shutdown::Graceful);
+    assert_eq!(start.elapsed(), Duration::ZERO);
+}
+
+#[tokio::test(start_paused = true)]
+async fn shutdown_of_a_link_without_a_task_waits_at_most_grace() {
+    let (tx, _rx) = tokio::sync::mpsc::channel(1);
+    let link = Link::<Stubborn>::new(tx, CancelToken::new(), ());
+    let start = tokio::time::Instant::now();
+
+    assert_eq!(link.shutdown((), Duration::from_secs(1)).await, Shutdown::Forced);
+    assert_eq!(link.to_dyn::<()>().shutdown(Duration::from_secs(1)).await, Shutdown::Forced);
+    assert_eq!(start.elapsed(), Duration::from_secs(2));
+}
@@ -0,0 +1,99 @@
+use actor12::Actor;
+use actor12::ActorError;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+struct Connection {
+    connected: bool,
+    handled: Vec<u32>,
+}
+
+impl Actor for Connection {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn stash_capacity() -> usize {
+        3
+    }
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Connection {
+            connected: false,
+            handled: Vec::new(),
+        }))
+    }
+}
+
+struct Query(u32);
+struct Connect;
+struct Handled;
+
+impl Handler<Query> for Connection {
+    type Reply = anyhow::Result<u32>;
+
+    async fn handle(&mut self, mut ctx: Call<'_, Self, Self::Reply>, query: Query) -> Self::Reply {
+        if !self.connected {
+            return ctx.stash(query);
+        }
+        self.handled.push(query.0);
+        Ok(query.0 * 10)
+    }
+}
+
+impl Handler<Connect> for Connection {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, mut ctx: Call<'_, Self, Self::Reply>, _: Connect) -> Self::Reply {
+        self.connected = true;
+        ctx.unstash_all();
+        Ok(())
+    }
+}
+
+impl Handler<Handled> for Connection {
+    type Reply = anyhow::Result<Vec<u32>>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Handled) -> Self::Reply {
+        Ok(self.handled.clone())
+    }
+}
+
+#[tokio::test]
+async fn unstashed_messages_come_before_the_mailbox() {
+    let link = actor12::spawn::<Connection>(());
+
+    let mut replies = Vec::new();
+    for n in 1..=3 {
+        replies.push(link.ask_dyn_async(Query(n)).await);
+    }
+    link.tell_dyn(Connect).await;
+    replies.push(link.ask_dyn_async(Query(4)).await);
+
+    for (reply, n) in replies.into_iter().zip(1..) {
+        assert_eq!(reply.await.unwrap(), n * 10);
+    }
+    assert_eq!(link.ask_dyn(Handled).await.unwrap(), [1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn full_stash_rejects_the_message() {
+    let link = actor12::spawn::<Connection>(());
+
+    let mut stashed = Vec::new();
+    for n in 1..=3 {
+        stashed.push(link.ask_dyn_async(Query(n)).await);
+    }
+
+    let err = link.ask_dyn(Query(4)).await.unwrap_err();
+    assert!(matches!(err.downcast_ref::<ActorError>(), Some(ActorError::StashFull)));
+}
@@ -0,0 +1,188 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorContext;
+use actor12::ActorExit;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::Link;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Strategy;
+use actor12::Supervisor;
+use actor12::Terminate;
+use actor12::cancel::CancelReason;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+struct Child;
+
+impl Actor for Child {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = u32;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Child))
+    }
+}
+
+struct Stop;
+
+impl Handler<Stop> for Child {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, ctx: Call<'_, Self, Self::Reply>, _: Stop) -> Self::Reply {
+        ctx.token.cancel(());
+        Ok(())
+    }
+}
+
+struct Crash;
+
+impl Handler<Crash> for Child {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Crash) -> Self::Reply {
+        panic!("boom");
+    }
+}
+
+/// Counts its live instances and fails to start while another one is live.
+struct Slow {
+    live: Arc<AtomicUsize>,
+}
+
+impl Actor for Slow {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = Arc<AtomicUsize>;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        assert_eq!(ctx.spec.fetch_add(1, Ordering::SeqCst), 0, "two instances are live");
+        future::ready(Ok(Slow { live: ctx.spec }))
+    }
+
+    fn termination_strategy(&mut self) -> Terminate {
+        Terminate::ProcessAll
+    }
+
+    async fn terminate(self, _ctx: ActorContext<Self>, _reason: CancelReason<Self::Cancel>) {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        self.live.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Handler<Stop> for Slow {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, ctx: Call<'_, Self, Self::Reply>, _: Stop) -> Self::Reply {
+        ctx.token.cancel(());
+        Ok(())
+    }
+}
+
+async fn restarted(supervisor: &Supervisor<Child>, index: usize, old: &Link<Child>) -> Link<Child> {
+    old.wait().await;
+    loop {
+        let current = supervisor.child(index).unwrap();
+        if &current != old {
+            return current;
+        }
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test]
+async fn one_for_one_restarts_only_the_stopped_child() {
+    let supervisor = Supervisor::<Child>::new(Strategy::OneForOne, vec![0, 1, 2]);
+    let before = supervisor.children();
+
+    before[1].ask_dyn(Stop).await.unwrap();
+    let after = restarted(&supervisor, 1, &before[1]).await;
+
+    assert!(after.alive());
+    assert_eq!(supervisor.child(0).unwrap(), before[0]);
+    assert_eq!(supervisor.child(2).unwrap(), before[2]);
+}
+
+#[tokio::test]
+async fn rest_for_one_restarts_later_children() {
+    let supervisor = Supervisor::<Child>::new(Strategy::RestForOne, vec![0, 1, 2]);
+    let before = supervisor.children();
+
+    before[1].ask_dyn(Stop).await.unwrap();
+    restarted(&supervisor, 1, &before[1]).await;
+
+    let after = supervisor.children();
+    assert_eq!(after[0], before[0]);
+    assert_ne!(after[2], before[2]);
+    assert!(!before[2].alive());
+}
+
+#[tokio::test]
+async fn one_for_all_restarts_every_child() {
+    let supervisor = Supervisor::<Child>::new(Strategy::OneForAll, vec![0, 1, 2]);
+    let before = supervisor.children();
+
+    before[2].ask_dyn(Stop).await.unwrap();
+    restarted(&supervisor, 2, &before[2]).await;
+
+    let after = supervisor.children();
+    for (old, new) in before.iter().zip(&after) {
+        assert!(!old.alive());
+        assert_ne!(old, new);
+    }
+}
+
+#[tokio::test]
+async fn shutdown_stops_children() {
+    let supervisor = Supervisor::<Child>::new(Strategy::OneForOne, vec![0, 1]);
+    let children = supervisor.children();
+
+    supervisor.shutdown().await;
+
+    assert!(children.iter().all(|child| !child.alive()));
+}
+
+#[tokio::test]
+async fn one_for_one_restarts_a_crashed_child() {
+    let supervisor = Supervisor::<Child>::new(Strategy::OneForOne, vec![0, 1]);
+    let before = supervisor.children();
+
+    assert!(before[0].ask_dyn(Crash).await.is_err());
+    let after = restarted(&supervisor, 0, &before[0]).await;
+
+    assert!(matches!(before[0].join().await, ActorExit::Crashed(_)));
+    assert!(after.alive());
+    assert!(after.ask_dyn(Stop).await.is_ok());
+    assert_eq!(supervisor.child(1).unwrap(), before[1]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn restart_waits_for_terminate() {
+    let live = Arc::new(AtomicUsize::new(0));
+    let supervisor = Supervisor::<Slow>::new(Strategy::OneForOne, vec![live.clone()]);
+    let before = supervisor.child(0).unwrap();
+
+    before.ask_dyn(Stop).await.unwrap();
+    while supervisor.child(0).unwrap() == before {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+    assert!(before.join().await.is_clean());
+
+    supervisor.shutdown().await;
+    assert_eq!(live.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorContext;
+use actor12::ActorSystem;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::cancel::CancelReason;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+type Log = Arc<Mutex<Vec<u32>>>;
+
+struct Worker {
+    id: u32,
+    log: Log,
+}
+
+impl Actor for Worker {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = (u32, Log);
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        let (id, log) = ctx.spec;
+        future::ready(Ok(Worker { id, log }))
+    }
+
+    async fn terminate(self, _ctx: ActorContext<Self>, _reason: CancelReason<Self::Cancel>) {
+        // Later workers take less time, so only a sequential shutdown keeps
+        // the log in reverse spawn order.
+        tokio::time::sleep(Duration::from_millis(10 * u64::from(self.id))).await;
+        self.log.lock().unwrap().push(self.id);
+    }
+}
+
+/// Spawns a worker as its child.
+struct Team {
+    _lead: actor12::Link<Worker>,
+}
+
+impl Actor for Team {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = Log;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(mut ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        let lead = ctx.spawn_child::<Worker>((1, ctx.spec.clone()));
+        future::ready(Ok(Team { _lead: lead }))
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn shutdown_stops_actors_newest_first() {
+    let system = ActorSystem::new();
+    let log = Log::default();
+
+    let links: Vec<_> = (1..=3)
+        .map(|id| system.spawn::<Worker>((id, log.clone())))
+        .collect();
+    assert_eq!(system.len(), 3);
+
+    system.shutdown().await;
+
+    assert_eq!(*log.lock().unwrap(), [3, 2, 1]);
+    assert!(system.is_empty());
+    assert!(system.token().is_cancelled());
+    assert!(links.iter().all(|link| !link.alive()));
+}
+
+#[tokio::test(start_paused = true)]
+async fn systems_keep_separate_stats() {
+    let first = ActorSystem::new();
+    let second = ActorSystem::new();
+
+    let _a = first.spawn::<Worker>((1, Log::default()));
+    let _b = first.spawn::<Worker>((2, Log::default()));
+    let _c = second.spawn::<Worker>((3, Log::default()));
+
+    assert_eq!(first.stats().live::<Worker>(), 2);
+    assert_eq!(second.stats().live::<Worker>(), 1);
+
+    first.shutdown().await;
+
+    assert_eq!(first.stats().live::<Worker>(), 0);
+    assert_eq!(first.stats().total::<Worker>(), 2);
+    assert_eq!(second.stats().live::<Worker>(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn dropping_the_system_cancels_its_actors() {
+    let system = ActorSystem::new();
+    let link = system.spawn::<Worker>((1, Log::default()));
+
+    drop(system);
+
+    assert!(link.join().await.is_clean());
+}
+
+#[tokio::test(start_paused = true)]
+async fn children_join_their_parents_system() {
+    let system = ActorSystem::new();
+    let log = Log::default();
+    let team = system.spawn::<Team>(log.clone());
+    team.ready().await.unwrap();
+
+    assert_eq!(system.len(), 2);
+    assert_eq!(system.stats().live::<Worker>(), 1);
+
+    system.shutdown().await;
+    assert_eq!(*log.lock().unwrap(), [1]);
+    assert!(!team.alive());
+
+    // The children's handle on the system does not keep it alive.
+    let system = ActorSystem::new();
+    let team = system.spawn::<Team>(Log::default());
+    team.ready().await.unwrap();
+    drop(system);
+    assert!(team.join().await.is_clean());
+}
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::Terminate;
+use actor12::prelude::InitFuture;
+use futures::future;
+
+struct Writer {
+    written: Arc<AtomicUsize>,
+}
+
+impl Actor for Writer {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = Arc<AtomicUsize>;
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(ctx: Init<'_, Self>) -> impl InitFuture<Self> {
+        future::ready(Ok(Writer { written: ctx.spec }))
+    }
+
+    fn termination_strategy(&mut self) -> Terminate {
+        Terminate::ProcessAll
+    }
+}
+
+struct Write;
+
+impl Handler<Write> for Writer {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Write) -> Self::Reply {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        self.written.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn process_all_drains_queued_messages() {
+    let written = Arc::new(AtomicUsize::new(0));
+    let link = actor12::spawn::<Writer>(written.clone());
+
+    for _ in 0..5 {
+        link.tell_dyn(Write).await;
+    }
+    link.cancel(());
+
+    assert!(link.join().await.is_clean());
+    assert_eq!(written.load(Ordering::SeqCst), 5);
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancel_and_wait_waits_for_the_drain() {
+    let written = Arc::new(AtomicUsize::new(0));
+    let link = actor12::spawn::<Writer>(written.clone());
+
+    for _ in 0..5 {
+        link.tell_dyn(Write).await;
+    }
+    link.cancel_and_wait(()).await;
+
+    assert_eq!(written.load(Ordering::SeqCst), 5);
+}
+
+#[tokio::test(start_paused = true)]
+async fn wait_and_dyn_cancel_and_wait_wait_for_the_drain() {
+    let written = Arc::new(AtomicUsize::new(0));
+    let link = actor12::spawn::<Writer>(written.clone());
+
+    for _ in 0..3 {
+        link.tell_dyn(Write).await;
+    }
+    link.to_dyn::<Write>().cancel_and_wait().await;
+    assert_eq!(written.load(Ordering::SeqCst), 3);
+
+    let link = actor12::spawn::<Writer>(written.clone());
+    link.tell_dyn(Write).await;
+    link.cancel(());
+    link.wait().await;
+    assert_eq!(written.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test(start_paused = true)]
+async fn process_all_rejects_new_messages() {
+    let written = Arc::new(AtomicUsize::new(0));
+    let link = actor12::spawn::<Writer>(written.clone());
+
+    link.tell_dyn(Write).await;
+    link.cancel(());
+    // The mailbox closes as soon as draining starts.
+    while link.alive() {
+        // Sleep rather than yield, so paused time can advance through the handler.
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+
+    assert!(link.ask_dyn(Write).await.is_err());
+    link.join().await;
+    assert_eq!(written.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::Call;
+use actor12::Envelope;
+use actor12::Exec;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::prelude::InitFuture;
+
+/// Takes a while to start, so its one-message mailbox stays full.
+struct Adder {
+    total: u32,
+}
+
+impl Actor for Adder {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn mailbox_capacity() -> usize {
+        1
+    }
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(Adder { total: 0 })
+        }
+    }
+}
+
+struct Add(u32);
+
+impl Handler<Add> for Adder {
+    type Reply = anyhow::Result<u32>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, msg: Add) -> Self::Reply {
+        self.total += msg.0;
+        Ok(self.total)
+    }
+}
+
+struct Doubler;
+
+impl Actor for Doubler {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Envelope<u32, anyhow::Result<u32>>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn mailbox_capacity() -> usize {
+        1
+    }
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        std::future::ready(Ok(Doubler))
+    }
+
+    async fn handle(&mut self, _ctx: Exec<'_, Self>, msg: Self::Message) {
+        let _ = msg.reply.send(Ok(msg.value * 2));
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn full_mailbox_hands_the_message_back() {
+    let link = actor12::spawn::<Adder>(());
+
+    link.try_tell_dyn(Add(1)).unwrap();
+    let full = link.try_tell_dyn(Add(2)).unwrap_err();
+    assert_eq!(full.0.0, 2);
+    let full = link.try_ask_dyn(Add(3)).err().unwrap();
+    assert_eq!(full.0.0, 3);
+
+    assert_eq!(link.ask_dyn(Add(0)).await.unwrap(), 1);
+    let reply = link.try_ask_dyn(Add(5)).ok().unwrap();
+    assert_eq!(reply.await.unwrap(), 6);
+}
+
+#[tokio::test]
+async fn try_send_to_single_message_actor() {
+    let link = actor12::spawn::<Doubler>(());
+
+    let first = link.try_send(2).ok().unwrap();
+    let full = link.try_send(3).err().unwrap();
+    assert_eq!(full.0, 3);
+
+    assert_eq!(first.await.unwrap(), 4);
+}
+
+#[tokio::test]
+async fn try_send_to_dead_actor_fails_in_the_reply() {
+    let link = actor12::spawn::<Adder>(());
+    link.cancel_and_wait(()).await;
+
+    link.try_tell_dyn(Add(1)).unwrap();
+    let reply = link.try_ask_dyn(Add(1)).ok().unwrap();
+    assert!(reply.await.is_err());
+}
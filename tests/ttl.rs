@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::ActorError;
+use actor12::Call;
+use actor12::Envelope;
+use actor12::Handler;
+use actor12::Init;
+use actor12::MpscChannel;
+use actor12::Multi;
+use actor12::prelude::InitFuture;
+
+/// Takes a while to start, so messages queue up before it receives any.
+/// Stashes records while paused.
+struct Recorder {
+    log: Vec<&'static str>,
+    paused: bool,
+}
+
+impl Actor for Recorder {
+    type Cancel = ();
+    type State = ();
+    type Channel = MpscChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(Recorder {
+                log: Vec::new(),
+                paused: false,
+            })
+        }
+    }
+}
+
+struct Record(&'static str);
+struct Log;
+struct Pause;
+struct Resume;
+
+impl Handler<Record> for Recorder {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, mut ctx: Call<'_, Self, Self::Reply>, msg: Record) -> Self::Reply {
+        if self.paused {
+            return ctx.stash(msg);
+        }
+        self.log.push(msg.0);
+        Ok(())
+    }
+}
+
+impl Handler<Pause> for Recorder {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Pause) -> Self::Reply {
+        self.paused = true;
+        Ok(())
+    }
+}
+
+impl Handler<Resume> for Recorder {
+    type Reply = anyhow::Result<()>;
+
+    async fn handle(&mut self, mut ctx: Call<'_, Self, Self::Reply>, _: Resume) -> Self::Reply {
+        self.paused = false;
+        ctx.unstash_all();
+        Ok(())
+    }
+}
+
+impl Handler<Log> for Recorder {
+    type Reply = anyhow::Result<Vec<&'static str>>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, _: Log) -> Self::Reply {
+        Ok(std::mem::take(&mut self.log))
+    }
+}
+
+fn is_expired(err: &anyhow::Error) -> bool {
+    matches!(err.downcast_ref::<ActorError>(), Some(ActorError::Expired))
+}
+
+#[tokio::test(start_paused = true)]
+async fn expired_messages_are_skipped() {
+    let link = actor12::spawn::<Recorder>(());
+
+    link.tell_dyn_with_ttl(Record("short"), Duration::from_millis(10)).await;
+    link.tell_dyn_with_ttl(Record("long"), Duration::from_secs(1)).await;
+    link.tell_dyn(Record("forever")).await;
+
+    let log = link.ask_dyn(Log).await.unwrap();
+    assert_eq!(log, ["long", "forever"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn expired_asks_resolve_to_expired() {
+    let link = actor12::spawn::<Recorder>(());
+
+    let expired = link.ask_dyn_with_ttl(Record("short"), Duration::from_millis(10));
+    let handled = link.ask_dyn_with_ttl(Record("long"), Duration::from_secs(1));
+    let (expired, handled) = tokio::join!(expired, handled);
+
+    assert!(is_expired(&expired.unwrap_err()));
+    handled.unwrap();
+    assert_eq!(link.ask_dyn(Log).await.unwrap(), ["long"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn ttl_does_not_cover_handling() {
+    let link = actor12::spawn::<Recorder>(());
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // The mailbox is drained right away, so the message never expires.
+    link.ask_dyn_with_ttl(Record("quick"), Duration::from_millis(1))
+        .await
+        .unwrap();
+    assert_eq!(link.ask_dyn(Log).await.unwrap(), ["quick"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn huge_ttls_never_expire() {
+    let link = actor12::spawn::<Recorder>(());
+
+    link.tell_dyn_with_ttl(Record("told"), Duration::MAX).await;
+    link.ask_dyn_with_ttl(Record("asked"), Duration::MAX).await.unwrap();
+    assert_eq!(link.ask_dyn(Log).await.unwrap(), ["told", "asked"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn stashed_messages_keep_their_ttl() {
+    let link = actor12::spawn::<Recorder>(());
+    link.ask_dyn(Pause).await.unwrap();
+
+    let untimed = link.ask_dyn_async(Record("untimed")).await;
+    let short = tokio::spawn({
+        let link = link.clone();
+        async move { link.ask_dyn_with_ttl(Record("short"), Duration::from_millis(10)).await }
+    });
+    let long = tokio::spawn({
+        let link = link.clone();
+        async move { link.ask_dyn_with_ttl(Record("long"), Duration::from_secs(1)).await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    link.ask_dyn(Resume).await.unwrap();
+    untimed.await.unwrap();
+    assert!(is_expired(&short.await.unwrap().unwrap_err()));
+    long.await.unwrap().unwrap();
+    assert_eq!(link.ask_dyn(Log).await.unwrap(), ["untimed", "long"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn multi_can_be_built_from_its_handler() {
+    let link = actor12::spawn::<Recorder>(());
+
+    let (envelope, rx) = Envelope::<Record, anyhow::Result<()>>::new(Record("raw"));
+    let multi = Multi::<Recorder>::new(envelope);
+    link.send_raw(Multi { handler: multi.handler }).await.unwrap();
+    rx.await.unwrap().unwrap();
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use actor12::Actor;
+use actor12::Call;
+use actor12::Handler;
+use actor12::Init;
+use actor12::Multi;
+use actor12::UnboundedChannel;
+use actor12::prelude::InitFuture;
+
+/// Takes a while to start, so messages pile up in its mailbox.
+struct Adder {
+    total: u32,
+}
+
+impl Actor for Adder {
+    type Cancel = ();
+    type State = ();
+    type Channel = UnboundedChannel<Self::Message>;
+    type Message = Multi<Self>;
+    type Spec = ();
+
+    fn state(_: &Self::Spec) -> Self::State {}
+
+    fn mailbox_capacity() -> usize {
+        4
+    }
+
+    fn init(_: Init<'_, Self>) -> impl InitFuture<Self> {
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(Adder { total: 0 })
+        }
+    }
+}
+
+struct Add(u32);
+
+impl Handler<Add> for Adder {
+    type Reply = anyhow::Result<u32>;
+
+    async fn handle(&mut self, _ctx: Call<'_, Self, Self::Reply>, msg: Add) -> Self::Reply {
+        self.total += msg.0;
+        Ok(self.total)
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn senders_never_wait() {
+    let link = actor12::spawn::<Adder>(());
+
+    for n in 1..=50 {
+        link.tell_dyn(Add(n)).await;
+    }
+    for n in 51..=100 {
+        link.try_tell_dyn(Add(n)).unwrap();
+    }
+    assert_eq!(link.len(), 100);
+
+    assert_eq!(link.ask_dyn(Add(0)).await.unwrap(), 5050);
+    assert!(link.is_empty());
+}
+
+#[tokio::test]
+async fn permits_and_closing() {
+    let link = actor12::spawn::<Adder>(());
+
+    let permit = link.reserve().await.unwrap();
+    assert_eq!(permit.ask_dyn(Add(2)).await.unwrap(), 2);
+
+    link.cancel_and_wait(()).await;
+    assert!(link.reserve().await.is_err());
+    assert!(link.ask_dyn(Add(1)).await.is_err());
+}